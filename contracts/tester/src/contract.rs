@@ -5,7 +5,7 @@ use cosmwasm_std::{
 
 use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
 use terra_cosmwasm::{
-    create_swap_msg, create_swap_send_msg, ContractInfoResponse, ExchangeRatesResponse,
+    create_swap_msg, create_swap_send_msg, ContractInfoResponse, Denom, ExchangeRatesResponse,
     SwapResponse, TaxCapResponse, TaxRateResponse, TerraMsgWrapper, TerraQuerier,
 };
 
@@ -45,6 +45,7 @@ pub fn execute_msg_swap(
     ask_denom: String,
     recipient: Option<Addr>,
 ) -> StdResult<Response<TerraMsgWrapper>> {
+    let ask_denom = Denom::new(ask_denom)?;
     let msg = if let Some(recipient) = recipient {
         create_swap_send_msg(recipient.to_string(), offer_coin, ask_denom)
     } else {
@@ -1,3 +1,10 @@
 mod math;
+mod signed;
 
-pub use crate::math::{Decimal256, Uint256};
+#[cfg(all(feature = "std-interop", not(target_arch = "wasm32")))]
+pub mod native;
+
+pub use crate::math::{
+    CheckedFromRatioError, Decimal256, FixedDecimal, RoundingMode, Uint256, Uint512,
+};
+pub use crate::signed::{Int256, SignedDecimal256};
@@ -1,126 +1,467 @@
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
 use schemars::JsonSchema;
 use serde::{de, ser, Deserialize, Deserializer, Serialize};
+use std::borrow::Borrow;
 use std::convert::TryFrom;
 use std::fmt::{self, Write};
+use std::iter;
 use std::ops;
 use std::str::FromStr;
 
-use bigint::U256;
-use cosmwasm_std::{Decimal, StdError, Uint128};
+use bigint::{FromDecStrErr, U256, U512};
+use cosmwasm_std::{
+    ConversionOverflowError, Decimal, DivideByZeroError, OverflowError, OverflowOperation,
+    StdError, StdResult, Uint128,
+};
+
+/// CheckedFromRatioError is returned by the checked division of decimals
+#[derive(Debug, PartialEq, Eq)]
+pub enum CheckedFromRatioError {
+    DivideByZero(DivideByZeroError),
+    /// The quotient does not fit in the target type
+    Overflow {
+        target_type: &'static str,
+        dividend: String,
+        divisor: String,
+    },
+}
+
+impl fmt::Display for CheckedFromRatioError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckedFromRatioError::DivideByZero(err) => write!(f, "{}", err),
+            CheckedFromRatioError::Overflow {
+                target_type,
+                dividend,
+                divisor,
+            } => write!(
+                f,
+                "{} division overflow: {} / {}",
+                target_type, dividend, divisor
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CheckedFromRatioError {}
+
+/// DivideByZero keeps its StdError variant, overflows become generic errors
+impl From<CheckedFromRatioError> for StdError {
+    fn from(err: CheckedFromRatioError) -> Self {
+        match err {
+            CheckedFromRatioError::DivideByZero(err) => err.into(),
+            err => StdError::generic_err(err.to_string()),
+        }
+    }
+}
+
+/// Implements `&a op b`, `a op &b` and `&a op &b` for a Copy type given `a op b`,
+/// optionally with the generic parameters given in brackets after `impl`
+macro_rules! forward_ref_binop {
+    (impl $imp:ident, $method:ident for $t:ty, $u:ty) => {
+        forward_ref_binop!(impl[] $imp, $method for $t, $u);
+    };
+    (impl[$($gen:tt)*] $imp:ident, $method:ident for $t:ty, $u:ty) => {
+        impl<'a, $($gen)*> ops::$imp<$u> for &'a $t {
+            type Output = <$t as ops::$imp<$u>>::Output;
+
+            fn $method(self, other: $u) -> Self::Output {
+                ops::$imp::$method(*self, other)
+            }
+        }
+
+        impl<'a, $($gen)*> ops::$imp<&'a $u> for $t {
+            type Output = <$t as ops::$imp<$u>>::Output;
+
+            fn $method(self, other: &'a $u) -> Self::Output {
+                ops::$imp::$method(self, *other)
+            }
+        }
+
+        impl<'a, 'b, $($gen)*> ops::$imp<&'a $u> for &'b $t {
+            type Output = <$t as ops::$imp<$u>>::Output;
+
+            fn $method(self, other: &'a $u) -> Self::Output {
+                ops::$imp::$method(*self, *other)
+            }
+        }
+    };
+}
+
+/// Implements `a op= &b` given `a op= b`
+macro_rules! forward_ref_op_assign {
+    (impl $imp:ident, $method:ident for $t:ty, $u:ty) => {
+        forward_ref_op_assign!(impl[] $imp, $method for $t, $u);
+    };
+    (impl[$($gen:tt)*] $imp:ident, $method:ident for $t:ty, $u:ty) => {
+        impl<'a, $($gen)*> ops::$imp<&'a $u> for $t {
+            fn $method(&mut self, other: &'a $u) {
+                ops::$imp::$method(self, *other);
+            }
+        }
+    };
+}
+
+/// How to round results that cannot be represented exactly
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Towards zero, i.e. truncation
+    Floor,
+    /// Away from zero
+    Ceil,
+    /// To the nearest value, ties away from zero
+    HalfUp,
+    /// To the nearest value, ties to the even neighbour (banker's rounding, as sdk.Dec does)
+    HalfEven,
+}
+
+/// Returns num / denom rounded according to `mode`. `denom` must not be zero.
+fn div_rounded(num: U512, denom: U512, mode: RoundingMode) -> U512 {
+    let quotient = num / denom;
+    let remainder = num % denom;
+    if remainder.is_zero() {
+        return quotient;
+    }
+
+    // callers pass denominators of at most 256 bits, so doubling the remainder cannot overflow
+    let round_up = match mode {
+        RoundingMode::Floor => false,
+        RoundingMode::Ceil => true,
+        RoundingMode::HalfUp => remainder + remainder >= denom,
+        RoundingMode::HalfEven => {
+            let twice = remainder + remainder;
+            twice > denom || (twice == denom && quotient.low_u64() & 1 == 1)
+        }
+    };
+
+    if round_up {
+        quotient + U512::one()
+    } else {
+        quotient
+    }
+}
+
+/// Error for a decimal string whose value does not fit in `type_name`
+fn parse_overflow(input: &str, type_name: &str) -> StdError {
+    StdError::generic_err(format!("Value {} overflows {}", input, type_name))
+}
+
+/// Maps the errors of from_dec_str, `part` names what was parsed in the error message
+fn parse_error(err: FromDecStrErr, part: &str, input: &str, type_name: &str) -> StdError {
+    match err {
+        FromDecStrErr::InvalidLength => parse_overflow(input, type_name),
+        FromDecStrErr::InvalidCharacter => StdError::generic_err(format!("Error parsing {}", part)),
+    }
+}
+
+/// Names of the FixedDecimal precisions, indexed by DIGITS
+const TYPE_NAMES: [&str; 39] = [
+    "FixedDecimal0",
+    "FixedDecimal1",
+    "FixedDecimal2",
+    "FixedDecimal3",
+    "FixedDecimal4",
+    "FixedDecimal5",
+    "FixedDecimal6",
+    "FixedDecimal7",
+    "FixedDecimal8",
+    "FixedDecimal9",
+    "FixedDecimal10",
+    "FixedDecimal11",
+    "FixedDecimal12",
+    "FixedDecimal13",
+    "FixedDecimal14",
+    "FixedDecimal15",
+    "FixedDecimal16",
+    "FixedDecimal17",
+    "Decimal256",
+    "FixedDecimal19",
+    "FixedDecimal20",
+    "FixedDecimal21",
+    "FixedDecimal22",
+    "FixedDecimal23",
+    "FixedDecimal24",
+    "FixedDecimal25",
+    "FixedDecimal26",
+    "FixedDecimal27",
+    "FixedDecimal28",
+    "FixedDecimal29",
+    "FixedDecimal30",
+    "FixedDecimal31",
+    "FixedDecimal32",
+    "FixedDecimal33",
+    "FixedDecimal34",
+    "FixedDecimal35",
+    "FixedDecimal36",
+    "FixedDecimal37",
+    "FixedDecimal38",
+];
+
+/// Returns 10^exp, for exp <= 38
+const fn pow10(exp: u32) -> U256 {
+    assert!(
+        exp <= 38,
+        "FixedDecimal supports at most 38 fractional digits"
+    );
+    let mut val = 1u128;
+    let mut i = 0;
+    while i < exp {
+        val *= 10;
+        i += 1;
+    }
+    U256([val as u64, (val >> 64) as u64, 0, 0])
+}
+
+/// A fixed-point decimal value with `DIGITS` fractional digits, stored as the value times 10^DIGITS,
+/// i.e. FixedDecimal::<6>::new(1_000_000.into()) == 1.0. `DIGITS` can be at most 38.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FixedDecimal<const DIGITS: u32>(pub U256);
 
-/// A fixed-point decimal value with 18 fractional digits, i.e. Decimal256(1_000_000_000_000_000_000) == 1.0
+/// A fixed-point decimal value with 18 fractional digits, i.e. Decimal256::new(1_000_000_000_000_000_000) == 1.0
 /// The greatest possible value that can be represented is 115792089237316195423570985008687907853269984665640564039457.584007913129639935 (which is (2^128 - 1) / 10^18)
-#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord, JsonSchema)]
-pub struct Decimal256(#[schemars(with = "String")] pub U256);
+pub type Decimal256 = FixedDecimal<18>;
 
-impl Decimal256 {
-    pub const MAX: Decimal256 = Decimal256(U256::MAX);
-    pub const DECIMAL_FRACTIONAL: U256 = U256([1_000_000_000_000_000_000u64, 0, 0, 0]);
+impl<const DIGITS: u32> FixedDecimal<DIGITS> {
+    pub const MAX: Self = FixedDecimal(U256::MAX);
+    pub const DECIMAL_FRACTIONAL: U256 = pow10(DIGITS);
+    pub const DECIMAL_PLACES: u32 = DIGITS;
 
-    /// Create a 1.0 Decimal256
-    pub const fn one() -> Decimal256 {
-        Decimal256(Decimal256::DECIMAL_FRACTIONAL)
+    /// Creates a decimal from its raw value, i.e. the value times 10^DIGITS
+    pub const fn new(value: U256) -> Self {
+        FixedDecimal(value)
+    }
+
+    /// Create a 1.0 decimal
+    pub const fn one() -> Self {
+        FixedDecimal(Self::DECIMAL_FRACTIONAL)
     }
 
-    /// Create a 0.0 Decimal256
-    pub const fn zero() -> Decimal256 {
-        Decimal256(U256([0, 0, 0, 0]))
+    /// Create a 0.0 decimal
+    pub const fn zero() -> Self {
+        FixedDecimal(U256([0, 0, 0, 0]))
+    }
+
+    /// Convert x% into a decimal, truncated to `DIGITS` fractional digits
+    pub fn percent(x: u64) -> Self {
+        Self::from_ratio(x, 100u64)
     }
 
-    /// Convert x% into Decimal256
-    pub fn percent(x: u64) -> Decimal256 {
-        Decimal256(U256::from(x) * U256::from(10_000_000_000_000_000u64))
+    /// Convert permille (x/1000) into a decimal, truncated to `DIGITS` fractional digits
+    pub fn permille(x: u64) -> Self {
+        Self::from_ratio(x, 1000u64)
     }
 
-    /// Convert permille (x/1000) into Decimal256
-    pub fn permille(x: u64) -> Decimal256 {
-        Decimal256(U256::from(x) * U256::from(1_000_000_000_000_000u64))
+    /// Name used in error messages and the JSON schema, e.g. `FixedDecimal6`;
+    /// `Decimal256` keeps its historic name
+    fn type_name() -> &'static str {
+        TYPE_NAMES[DIGITS as usize]
     }
 
-    /// Returns the ratio (nominator / denominator) as a Decimal256
-    pub fn from_ratio<A: Into<U256>, B: Into<U256>>(nominator: A, denominator: B) -> Decimal256 {
+    /// Returns the ratio (nominator / denominator) as a decimal
+    pub fn from_ratio<A: Into<U256>, B: Into<U256>>(nominator: A, denominator: B) -> Self {
         let nominator: U256 = nominator.into();
         let denominator: U256 = denominator.into();
         if denominator.is_zero() {
             panic!("Denominator must not be zero");
         }
 
-        Decimal256(nominator * Decimal256::DECIMAL_FRACTIONAL / denominator)
+        let val = Uint512::from(nominator.full_mul(Self::DECIMAL_FRACTIONAL))
+            / Uint512::from(denominator);
+        match Uint256::try_from(val) {
+            Ok(val) => FixedDecimal(val.0),
+            Err(_) => panic!("Ratio overflows {}", Self::type_name()),
+        }
     }
 
-    pub fn from_uint256<A: Into<Uint256>>(val: A) -> Decimal256 {
+    pub fn from_uint256<A: Into<Uint256>>(val: A) -> Self {
         let num: Uint256 = val.into();
-        Decimal256(num.0 * Decimal256::DECIMAL_FRACTIONAL)
+        FixedDecimal(num.0 * Self::DECIMAL_FRACTIONAL)
     }
 
     pub fn is_zero(&self) -> bool {
         self.0.is_zero()
     }
+
+    /// Largest integer value not greater than self
+    pub fn floor(&self) -> Self {
+        self.round_with(0, RoundingMode::Floor)
+    }
+
+    /// Smallest integer value not less than self
+    pub fn ceil(&self) -> Self {
+        self.round_with(0, RoundingMode::Ceil)
+    }
+
+    /// Rounds half up to `dp` fractional digits
+    pub fn round(&self, dp: u32) -> Self {
+        self.round_with(dp, RoundingMode::HalfUp)
+    }
+
+    /// Rounds to `dp` fractional digits according to `mode`
+    pub fn round_with(&self, dp: u32, mode: RoundingMode) -> Self {
+        if dp >= Self::DECIMAL_PLACES {
+            return *self;
+        }
+
+        let factor = U256::from(10u64).pow((Self::DECIMAL_PLACES - dp).into());
+        let val = div_rounded(self.0.into(), factor.into(), mode);
+        match narrow_u512(val).and_then(|val| narrow_u512(val.full_mul(factor))) {
+            Some(val) => FixedDecimal(val),
+            None => panic!("Rounding overflows {}", Self::type_name()),
+        }
+    }
+
+    /// Converts to Uint256, rounding according to `mode`
+    pub fn to_uint(&self, mode: RoundingMode) -> Uint256 {
+        Uint256(
+            narrow_u512(div_rounded(
+                self.0.into(),
+                Self::DECIMAL_FRACTIONAL.into(),
+                mode,
+            ))
+            .unwrap(),
+        )
+    }
+
+    /// Integer part of self
+    pub fn to_uint_floor(&self) -> Uint256 {
+        self.to_uint(RoundingMode::Floor)
+    }
+
+    /// Integer part of self, plus one if self has a fractional part
+    pub fn to_uint_ceil(&self) -> Uint256 {
+        self.to_uint(RoundingMode::Ceil)
+    }
+
+    /// Converts to `TARGET` fractional digits, rounding according to `mode` when digits are dropped.
+    /// Fails when the value does not fit the target precision.
+    pub fn rescale<const TARGET: u32>(
+        &self,
+        mode: RoundingMode,
+    ) -> Result<FixedDecimal<TARGET>, ConversionOverflowError> {
+        let val = if TARGET >= DIGITS {
+            narrow_u512(self.0.full_mul(pow10(TARGET - DIGITS)))
+        } else {
+            narrow_u512(div_rounded(
+                self.0.into(),
+                pow10(DIGITS - TARGET).into(),
+                mode,
+            ))
+        };
+        val.map(FixedDecimal).ok_or_else(|| {
+            ConversionOverflowError::new(
+                Self::type_name(),
+                FixedDecimal::<TARGET>::type_name(),
+                self.to_string(),
+            )
+        })
+    }
+
+    /// Converts to `TARGET` fractional digits without any rounding.
+    /// Fails when the value does not fit the target precision or has more than `TARGET` fractional digits.
+    pub fn checked_rescale<const TARGET: u32>(&self) -> StdResult<FixedDecimal<TARGET>> {
+        let rescaled = self.rescale::<TARGET>(RoundingMode::Floor)?;
+        if rescaled.rescale::<DIGITS>(RoundingMode::Floor)? != *self {
+            return Err(StdError::generic_err(format!(
+                "{} has more than {} fractional digits",
+                self, TARGET
+            )));
+        }
+        Ok(rescaled)
+    }
 }
 
 impl From<Decimal> for Decimal256 {
     fn from(val: Decimal) -> Self {
-        Decimal256::from_str(&val.to_string()).unwrap()
+        Self::from_str(&val.to_string()).unwrap()
     }
 }
 
-impl From<Decimal256> for Decimal {
-    fn from(n: Decimal256) -> Self {
+impl TryFrom<Decimal256> for Decimal {
+    type Error = ConversionOverflowError;
+
+    fn try_from(n: Decimal256) -> Result<Self, Self::Error> {
         let U256(ref arr) = n.0;
-        assert!(arr[2] == 0u64);
-        assert!(arr[3] == 0u64);
-        Decimal::from_str(&n.to_string()).unwrap()
+        if arr[2] != 0u64 || arr[3] != 0u64 {
+            return Err(ConversionOverflowError::new(
+                "Decimal256",
+                "Decimal",
+                n.to_string(),
+            ));
+        }
+        // Decimal holds 18 fractional digits in a u128, so every value below 2^128 parses
+        Ok(Decimal::from_str(&n.to_string()).unwrap())
     }
 }
 
-impl FromStr for Decimal256 {
+impl<const DIGITS: u32> FromStr for FixedDecimal<DIGITS> {
     type Err = StdError;
 
-    /// Converts the decimal string to a Decimal256
+    /// Converts the decimal string to a decimal
     /// Possible inputs: "1.23", "1", "000012", "1.123000000"
     /// Disallowed: "", ".23"
     ///
     /// This never performs any kind of rounding.
-    /// More than 18 fractional digits, even zeros, result in an error.
+    /// More than `DIGITS` fractional digits, even zeros, result in an error,
+    /// as do values above MAX.
     fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let overflow = || parse_overflow(input, Self::type_name());
         let parts: Vec<&str> = input.split('.').collect();
         match parts.len() {
             1 => {
                 let whole = U256::from_dec_str(parts[0])
-                    .map_err(|_| StdError::generic_err("Error parsing whole"))?;
-
-                let whole_as_atomics = whole * Decimal256::DECIMAL_FRACTIONAL;
-                Ok(Decimal256(whole_as_atomics))
+                    .map_err(|e| parse_error(e, "whole", input, Self::type_name()))?;
+
+                let (whole_as_atomics, overflowed) =
+                    whole.overflowing_mul(Self::DECIMAL_FRACTIONAL);
+                if overflowed {
+                    return Err(overflow());
+                }
+                Ok(FixedDecimal(whole_as_atomics))
             }
             2 => {
                 let whole = U256::from_dec_str(parts[0])
-                    .map_err(|_| StdError::generic_err("Error parsing whole"))?;
+                    .map_err(|e| parse_error(e, "whole", input, Self::type_name()))?;
                 let fractional = U256::from_dec_str(parts[1])
-                    .map_err(|_| StdError::generic_err("Error parsing fractional"))?;
-                let exp = (18usize.checked_sub(parts[1].len())).ok_or_else(|| {
-                    StdError::generic_err("Cannot parse more than 18 fractional digits")
+                    .map_err(|e| parse_error(e, "fractional", input, Self::type_name()))?;
+                let exp = ((DIGITS as usize).checked_sub(parts[1].len())).ok_or_else(|| {
+                    StdError::generic_err(format!(
+                        "Cannot parse more than {} fractional digits",
+                        DIGITS
+                    ))
                 })?;
                 let fractional_factor = U256::from(10).pow(exp.into());
 
-                let whole_as_atomics = whole * Decimal256::DECIMAL_FRACTIONAL;
-                let atomics = whole_as_atomics + fractional * fractional_factor;
-                Ok(Decimal256(atomics))
+                // fractional * fractional_factor < DECIMAL_FRACTIONAL, so only these can overflow
+                let (whole_as_atomics, overflowed) =
+                    whole.overflowing_mul(Self::DECIMAL_FRACTIONAL);
+                if overflowed {
+                    return Err(overflow());
+                }
+                let (atomics, overflowed) =
+                    whole_as_atomics.overflowing_add(fractional * fractional_factor);
+                if overflowed {
+                    return Err(overflow());
+                }
+                Ok(FixedDecimal(atomics))
             }
             _ => Err(StdError::generic_err("Unexpected number of dots")),
         }
     }
 }
 
-impl fmt::Display for Decimal256 {
+impl<const DIGITS: u32> fmt::Display for FixedDecimal<DIGITS> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let whole = (self.0) / Decimal256::DECIMAL_FRACTIONAL;
-        let fractional = (self.0) % Decimal256::DECIMAL_FRACTIONAL;
+        let whole = (self.0) / Self::DECIMAL_FRACTIONAL;
+        let fractional = (self.0) % Self::DECIMAL_FRACTIONAL;
 
         if fractional.is_zero() {
             write!(f, "{}", whole)
         } else {
             let fractional_string = fractional.to_string();
-            let fractional_string = "0".repeat(18 - fractional_string.len()) + &fractional_string;
+            let fractional_string =
+                "0".repeat(DIGITS as usize - fractional_string.len()) + &fractional_string;
 
             f.write_str(&whole.to_string())?;
             f.write_char('.')?;
@@ -131,49 +472,325 @@ impl fmt::Display for Decimal256 {
     }
 }
 
-impl ops::Add for Decimal256 {
+impl<const DIGITS: u32> ops::Add for FixedDecimal<DIGITS> {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self {
-        Decimal256(self.0 + rhs.0)
+        FixedDecimal(self.0 + rhs.0)
     }
 }
 
-impl ops::AddAssign for Decimal256 {
+impl<const DIGITS: u32> ops::AddAssign for FixedDecimal<DIGITS> {
     fn add_assign(&mut self, rhs: Self) {
         self.0 = self.0 + rhs.0;
     }
 }
 
-impl ops::Sub for Decimal256 {
+impl<const DIGITS: u32> ops::Sub for FixedDecimal<DIGITS> {
     type Output = Self;
 
     fn sub(self, rhs: Self) -> Self {
         assert!(self.0 >= rhs.0);
-        Decimal256(self.0 - rhs.0)
+        FixedDecimal(self.0 - rhs.0)
     }
 }
 
-impl ops::Mul for Decimal256 {
+impl<const DIGITS: u32> ops::Mul for FixedDecimal<DIGITS> {
     type Output = Self;
 
     fn mul(self, rhs: Self) -> Self {
-        Decimal256(self.0 * rhs.0 / Decimal256::DECIMAL_FRACTIONAL)
+        match self.checked_mul(rhs) {
+            Ok(val) => val,
+            Err(e) => panic!("{}", e),
+        }
     }
 }
 
-impl ops::Div for Decimal256 {
+impl<const DIGITS: u32> ops::Div for FixedDecimal<DIGITS> {
     type Output = Self;
 
     fn div(self, rhs: Self) -> Self {
         assert!(!rhs.is_zero());
 
-        Decimal256(self.0 * Decimal256::DECIMAL_FRACTIONAL / rhs.0)
+        match self.checked_div(rhs) {
+            Ok(val) => val,
+            Err(e) => panic!("{}", e),
+        }
+    }
+}
+
+impl<const DIGITS: u32> ops::Rem for FixedDecimal<DIGITS> {
+    type Output = Self;
+
+    fn rem(self, rhs: Self) -> Self {
+        assert!(!rhs.is_zero());
+
+        FixedDecimal(self.0 % rhs.0)
+    }
+}
+
+impl<const DIGITS: u32> ops::SubAssign for FixedDecimal<DIGITS> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const DIGITS: u32> ops::MulAssign for FixedDecimal<DIGITS> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const DIGITS: u32> ops::DivAssign for FixedDecimal<DIGITS> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl<const DIGITS: u32> ops::RemAssign for FixedDecimal<DIGITS> {
+    fn rem_assign(&mut self, rhs: Self) {
+        *self = *self % rhs;
+    }
+}
+
+forward_ref_binop!(impl[const DIGITS: u32] Add, add for FixedDecimal<DIGITS>, FixedDecimal<DIGITS>);
+forward_ref_binop!(impl[const DIGITS: u32] Sub, sub for FixedDecimal<DIGITS>, FixedDecimal<DIGITS>);
+forward_ref_binop!(impl[const DIGITS: u32] Mul, mul for FixedDecimal<DIGITS>, FixedDecimal<DIGITS>);
+forward_ref_binop!(impl[const DIGITS: u32] Div, div for FixedDecimal<DIGITS>, FixedDecimal<DIGITS>);
+forward_ref_binop!(impl[const DIGITS: u32] Rem, rem for FixedDecimal<DIGITS>, FixedDecimal<DIGITS>);
+forward_ref_op_assign!(impl[const DIGITS: u32] AddAssign, add_assign for FixedDecimal<DIGITS>, FixedDecimal<DIGITS>);
+forward_ref_op_assign!(impl[const DIGITS: u32] SubAssign, sub_assign for FixedDecimal<DIGITS>, FixedDecimal<DIGITS>);
+forward_ref_op_assign!(impl[const DIGITS: u32] MulAssign, mul_assign for FixedDecimal<DIGITS>, FixedDecimal<DIGITS>);
+forward_ref_op_assign!(impl[const DIGITS: u32] DivAssign, div_assign for FixedDecimal<DIGITS>, FixedDecimal<DIGITS>);
+forward_ref_op_assign!(impl[const DIGITS: u32] RemAssign, rem_assign for FixedDecimal<DIGITS>, FixedDecimal<DIGITS>);
+
+impl<const DIGITS: u32, A: Borrow<FixedDecimal<DIGITS>>> iter::Sum<A> for FixedDecimal<DIGITS> {
+    fn sum<I: Iterator<Item = A>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, x| acc + *x.borrow())
+    }
+}
+
+impl<const DIGITS: u32, A: Borrow<FixedDecimal<DIGITS>>> iter::Product<A> for FixedDecimal<DIGITS> {
+    fn product<I: Iterator<Item = A>>(iter: I) -> Self {
+        iter.fold(Self::one(), |acc, x| acc * *x.borrow())
+    }
+}
+
+/// Checked and saturating arithmetic. Products and quotients are computed with a
+/// 512-bit intermediate, so they only fail when the result itself is out of range.
+impl<const DIGITS: u32> FixedDecimal<DIGITS> {
+    pub fn checked_add(self, other: Self) -> Result<Self, OverflowError> {
+        match self.0.overflowing_add(other.0) {
+            (val, false) => Ok(FixedDecimal(val)),
+            (_, true) => Err(OverflowError::new(OverflowOperation::Add, self, other)),
+        }
+    }
+
+    pub fn checked_sub(self, other: Self) -> Result<Self, OverflowError> {
+        match self.0.overflowing_sub(other.0) {
+            (val, false) => Ok(FixedDecimal(val)),
+            (_, true) => Err(OverflowError::new(OverflowOperation::Sub, self, other)),
+        }
+    }
+
+    pub fn checked_mul(self, other: Self) -> Result<Self, OverflowError> {
+        let val = self.0.full_mul(other.0) / U512::from(Self::DECIMAL_FRACTIONAL);
+        narrow_u512(val)
+            .map(FixedDecimal)
+            .ok_or_else(|| OverflowError::new(OverflowOperation::Mul, self, other))
+    }
+
+    pub fn checked_div(self, other: Self) -> Result<Self, CheckedFromRatioError> {
+        if other.is_zero() {
+            return Err(CheckedFromRatioError::DivideByZero(DivideByZeroError::new(
+                self,
+            )));
+        }
+
+        let val = self.0.full_mul(Self::DECIMAL_FRACTIONAL) / U512::from(other.0);
+        narrow_u512(val)
+            .map(FixedDecimal)
+            .ok_or_else(|| CheckedFromRatioError::Overflow {
+                target_type: Self::type_name(),
+                dividend: self.to_string(),
+                divisor: other.to_string(),
+            })
+    }
+
+    pub fn saturating_add(self, other: Self) -> Self {
+        self.checked_add(other).unwrap_or(Self::MAX)
+    }
+
+    pub fn saturating_sub(self, other: Self) -> Self {
+        self.checked_sub(other).unwrap_or_else(|_| Self::zero())
+    }
+
+    pub fn saturating_mul(self, other: Self) -> Self {
+        self.checked_mul(other).unwrap_or(Self::MAX)
+    }
+
+    /// Returns MAX when the quotient overflows or `other` is zero. This includes 0 / 0, which
+    /// has no meaningful quotient but saturates like any other division by zero instead of
+    /// panicking.
+    pub fn saturating_div(self, other: Self) -> Self {
+        self.checked_div(other).unwrap_or(Self::MAX)
+    }
+}
+
+/// 1.0 with 36 decimal places, the working precision of exp and ln
+const PRECISE_ONE: U256 = U256([12919594847110692864, 54210108624275221, 0, 0]);
+/// ln(2) with 36 decimal places, i.e. 0.693147180559945309417232121458176568
+const PRECISE_LN_2: U256 = U256([9456716947207598648, 37575583950764745, 0, 0]);
+
+/// Powers and roots
+impl<const DIGITS: u32> FixedDecimal<DIGITS> {
+    /// Raises self to the power of `exp`. Every intermediate product is truncated to `DIGITS`
+    /// decimal places, so the result never exceeds the exact power. It falls short by a
+    /// relative error in the order of `exp * 10^-DIGITS` for bases of at least one, and by an
+    /// absolute error of that order for smaller bases.
+    pub fn checked_pow(self, exp: u32) -> Result<Self, OverflowError> {
+        let overflow =
+            || OverflowError::new(OverflowOperation::Pow, self.to_string(), exp.to_string());
+
+        let mut base = self;
+        let mut result = Self::one();
+        let mut n = exp;
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result.checked_mul(base).map_err(|_| overflow())?;
+            }
+            n >>= 1;
+            if n > 0 {
+                base = base.checked_mul(base).map_err(|_| overflow())?;
+            }
+        }
+
+        Ok(result)
+    }
+
+    pub fn pow(self, exp: u32) -> Self {
+        match self.checked_pow(exp) {
+            Ok(val) => val,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Square root truncated to `DIGITS` decimal places. This is exact: the result is the
+    /// largest decimal whose square does not exceed self.
+    pub fn sqrt(&self) -> Self {
+        let root = isqrt_u512(self.0.full_mul(Self::DECIMAL_FRACTIONAL));
+        // sqrt(2^256 * 10^38) < 2^192
+        FixedDecimal(narrow_u512(root).unwrap())
+    }
+}
+
+/// Exponentials and logarithms, whose working precision is tied to 18 decimal places
+impl Decimal256 {
+    /// e^self, computed with 36 decimal places to a relative error below 10^-30 and then
+    /// rounded half up to 18 decimal places. Fails when the result exceeds Decimal256::MAX.
+    pub fn exp(&self) -> StdResult<Decimal256> {
+        let overflow = || StdError::generic_err(format!("exp({}) overflows Decimal256", self));
+
+        // e^137 > Decimal256::MAX, and the bound keeps the 36 digit value below 2^256
+        if self.0 > U256::from(137u64) * Self::DECIMAL_FRACTIONAL {
+            return Err(overflow());
+        }
+
+        // e^x = 2^k * e^r with 0 <= r < ln(2)
+        let x = self.0 * Self::DECIMAL_FRACTIONAL;
+        let k = x / PRECISE_LN_2;
+        let r = x - k * PRECISE_LN_2;
+
+        // Taylor series of e^r, whose terms shrink at least geometrically
+        let mut term = PRECISE_ONE;
+        let mut sum = PRECISE_ONE;
+        let mut i = 1u64;
+        while !term.is_zero() {
+            term = term * r / (PRECISE_ONE * U256::from(i));
+            sum = sum + term;
+            i += 1;
+        }
+
+        // e^r < 2 and k < 198, so the shifted value fits in 512 bits
+        let val = div_rounded(
+            U512::from(sum) << k.as_u64() as usize,
+            Self::DECIMAL_FRACTIONAL.into(),
+            RoundingMode::HalfUp,
+        );
+        narrow_u512(val).map(FixedDecimal).ok_or_else(overflow)
+    }
+
+    /// Natural logarithm, computed with 36 decimal places to an absolute error below 10^-30
+    /// and then rounded half up to 18 decimal places. Decimal256 cannot represent negative
+    /// values, so self must be at least one.
+    pub fn ln(&self) -> StdResult<Decimal256> {
+        if self.0 < Self::DECIMAL_FRACTIONAL {
+            return Err(StdError::generic_err(format!(
+                "ln({}) is negative and cannot be represented as Decimal256",
+                self
+            )));
+        }
+
+        // self = 2^k * m with 1 <= m < 2
+        let precise_two = U512::from(PRECISE_ONE) << 1;
+        let mut m = self.0.full_mul(Self::DECIMAL_FRACTIONAL);
+        let mut k = 0u64;
+        while m >= precise_two {
+            m = m >> 1;
+            k += 1;
+        }
+        let m = narrow_u512(m).unwrap();
+
+        // ln(m) = 2 * atanh(z) = 2 * (z + z^3/3 + z^5/5 + ...) with z = (m - 1) / (m + 1) < 1/3
+        let z = (m - PRECISE_ONE) * PRECISE_ONE / (m + PRECISE_ONE);
+        let z2 = z * z / PRECISE_ONE;
+        let mut term = z;
+        let mut sum = U256::zero();
+        let mut i = 1u64;
+        while !term.is_zero() {
+            sum = sum + term / U256::from(i);
+            term = term * z2 / PRECISE_ONE;
+            i += 2;
+        }
+
+        let ln = sum * U256::from(2u64) + PRECISE_LN_2 * U256::from(k);
+        let val = div_rounded(
+            ln.into(),
+            Self::DECIMAL_FRACTIONAL.into(),
+            RoundingMode::HalfUp,
+        );
+        Ok(FixedDecimal(narrow_u512(val).unwrap()))
+    }
+}
+
+/// Largest integer whose square does not exceed n
+fn isqrt_u512(n: U512) -> U512 {
+    if n.is_zero() {
+        return n;
+    }
+
+    // Newton's method converges monotonically from any starting point above the root
+    let mut x = U512::one() << ((n.bits() + 1) / 2);
+    loop {
+        let y = (x + n / x) >> 1;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// Returns the value as U256 if it fits
+fn narrow_u512(val: U512) -> Option<U256> {
+    let U512(ref arr) = val;
+    if arr[4] | arr[5] | arr[6] | arr[7] != 0 {
+        return None;
     }
+    Some(U256([arr[0], arr[1], arr[2], arr[3]]))
 }
 
 /// Serializes as a decimal string
-impl Serialize for Decimal256 {
+impl<const DIGITS: u32> Serialize for FixedDecimal<DIGITS> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: ser::Serializer,
@@ -183,19 +800,19 @@ impl Serialize for Decimal256 {
 }
 
 /// Deserializes as a base64 string
-impl<'de> Deserialize<'de> for Decimal256 {
-    fn deserialize<D>(deserializer: D) -> Result<Decimal256, D::Error>
+impl<'de, const DIGITS: u32> Deserialize<'de> for FixedDecimal<DIGITS> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_str(Decimal256Visitor)
+        deserializer.deserialize_str(FixedDecimalVisitor::<DIGITS>)
     }
 }
 
-struct Decimal256Visitor;
+struct FixedDecimalVisitor<const DIGITS: u32>;
 
-impl<'de> de::Visitor<'de> for Decimal256Visitor {
-    type Value = Decimal256;
+impl<'de, const DIGITS: u32> de::Visitor<'de> for FixedDecimalVisitor<DIGITS> {
+    type Value = FixedDecimal<DIGITS>;
 
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         formatter.write_str("string-encoded decimal")
@@ -205,13 +822,24 @@ impl<'de> de::Visitor<'de> for Decimal256Visitor {
     where
         E: de::Error,
     {
-        match Decimal256::from_str(v) {
+        match FixedDecimal::from_str(v) {
             Ok(d) => Ok(d),
             Err(e) => Err(E::custom(format!("Error parsing decimal '{}': {}", v, e))),
         }
     }
 }
 
+/// Described as a string, like Decimal256 always was
+impl<const DIGITS: u32> JsonSchema for FixedDecimal<DIGITS> {
+    fn schema_name() -> String {
+        Self::type_name().to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
 //*** Uint256 ***/
 #[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord, JsonSchema)]
 pub struct Uint256(#[schemars(with = "String")] pub U256);
@@ -284,7 +912,7 @@ impl FromStr for Uint256 {
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
         let number =
-            U256::from_dec_str(input).map_err(|_| StdError::generic_err("Error parsing number"))?;
+            U256::from_dec_str(input).map_err(|e| parse_error(e, "number", input, "Uint256"))?;
         Ok(Uint256(number))
     }
 }
@@ -295,21 +923,31 @@ impl From<Uint256> for String {
     }
 }
 
-impl From<Uint256> for u128 {
-    fn from(n: Uint256) -> Self {
+impl TryFrom<Uint256> for u128 {
+    type Error = ConversionOverflowError;
+
+    fn try_from(n: Uint256) -> Result<Self, Self::Error> {
         let U256(ref arr) = n.0;
-        assert!(arr[2] == 0u64);
-        assert!(arr[3] == 0u64);
+        if arr[2] != 0u64 || arr[3] != 0u64 {
+            return Err(ConversionOverflowError::new(
+                "Uint256",
+                "u128",
+                n.to_string(),
+            ));
+        }
 
         let (hi, low) = (arr[1], arr[0]);
-        ((hi as u128) << 64) + (low as u128)
+        Ok(((hi as u128) << 64) + (low as u128))
     }
 }
 
-impl From<Uint256> for Uint128 {
-    fn from(n: Uint256) -> Self {
-        let num: u128 = n.into();
-        Uint128::from(num)
+impl TryFrom<Uint256> for Uint128 {
+    type Error = ConversionOverflowError;
+
+    fn try_from(n: Uint256) -> Result<Self, Self::Error> {
+        let num = u128::try_from(n)
+            .map_err(|_| ConversionOverflowError::new("Uint256", "Uint128", n.to_string()))?;
+        Ok(Uint128::from(num))
     }
 }
 
@@ -358,36 +996,36 @@ impl ops::Mul<Uint256> for Uint256 {
 
 /// Both d*u and u*d with d: Decimal256 and u: Uint256 returns an Uint256. There is no
 /// specific reason for this decision other than the initial use cases we have. If you
-/// need a Decimal256 result for the same calculation, use Decimal256(d*u) or Decimal256(u*d).
-impl ops::Mul<Decimal256> for Uint256 {
+/// need a Decimal256 result for the same calculation, use Decimal256::from_uint256(d*u).
+impl<const DIGITS: u32> ops::Mul<FixedDecimal<DIGITS>> for Uint256 {
     type Output = Self;
 
     #[allow(clippy::suspicious_arithmetic_impl)]
-    fn mul(self, rhs: Decimal256) -> Self::Output {
+    fn mul(self, rhs: FixedDecimal<DIGITS>) -> Self::Output {
         // 0*a and b*0 is always 0
         if self.is_zero() || rhs.is_zero() {
             return Uint256::zero();
         }
 
-        self.multiply_ratio(rhs.0, Decimal256::DECIMAL_FRACTIONAL)
+        self.multiply_ratio(rhs.0, FixedDecimal::<DIGITS>::DECIMAL_FRACTIONAL)
     }
 }
 
-impl ops::Div<Decimal256> for Uint256 {
+impl<const DIGITS: u32> ops::Div<FixedDecimal<DIGITS>> for Uint256 {
     type Output = Self;
 
-    fn div(self, rhs: Decimal256) -> Self::Output {
+    fn div(self, rhs: FixedDecimal<DIGITS>) -> Self::Output {
         assert!(!rhs.is_zero());
 
         if self.is_zero() {
             return Uint256::zero();
         }
 
-        self.multiply_ratio(Decimal256::DECIMAL_FRACTIONAL, rhs.0)
+        self.multiply_ratio(FixedDecimal::<DIGITS>::DECIMAL_FRACTIONAL, rhs.0)
     }
 }
 
-impl ops::Mul<Uint256> for Decimal256 {
+impl<const DIGITS: u32> ops::Mul<Uint256> for FixedDecimal<DIGITS> {
     type Output = Uint256;
 
     fn mul(self, rhs: Uint256) -> Self::Output {
@@ -395,97 +1033,542 @@ impl ops::Mul<Uint256> for Decimal256 {
     }
 }
 
-impl Uint256 {
-    /// returns self * nom / denom
-    pub fn multiply_ratio<A: Into<U256>, B: Into<U256>>(&self, nom: A, denom: B) -> Uint256 {
-        let nominator: U256 = nom.into();
-        let denominator: U256 = denom.into();
-        if denominator.is_zero() {
-            panic!("Denominator must not be zero");
-        }
+impl ops::Div<Uint256> for Uint256 {
+    type Output = Self;
 
-        // TODO: minimize rounding that takes place (using gcd algorithm)
-        let val = self.0 * nominator / denominator;
-        Uint256::from(val)
+    fn div(self, rhs: Uint256) -> Self::Output {
+        match self.checked_div(rhs) {
+            Ok(val) => val,
+            Err(e) => panic!("{}", e),
+        }
     }
 }
 
-/// Serializes as a base64 string
-impl Serialize for Uint256 {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: ser::Serializer,
-    {
-        serializer.serialize_str(&self.to_string())
+impl ops::Rem for Uint256 {
+    type Output = Self;
+
+    fn rem(self, rhs: Self) -> Self::Output {
+        match self.checked_rem(rhs) {
+            Ok(val) => val,
+            Err(e) => panic!("{}", e),
+        }
     }
 }
 
-/// Deserializes as a base64 string
-impl<'de> Deserialize<'de> for Uint256 {
-    fn deserialize<D>(deserializer: D) -> Result<Uint256, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        deserializer.deserialize_str(Uint256Visitor)
+impl ops::Shl<u32> for Uint256 {
+    type Output = Self;
+
+    fn shl(self, rhs: u32) -> Self::Output {
+        assert!(rhs < 256, "Shift of {} bits overflows Uint256", rhs);
+        Uint256(self.0 << rhs as usize)
     }
 }
 
-struct Uint256Visitor;
+impl ops::Shr<u32> for Uint256 {
+    type Output = Self;
 
-impl<'de> de::Visitor<'de> for Uint256Visitor {
-    type Value = Uint256;
+    fn shr(self, rhs: u32) -> Self::Output {
+        assert!(rhs < 256, "Shift of {} bits overflows Uint256", rhs);
+        Uint256(self.0 >> rhs as usize)
+    }
+}
 
-    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("string-encoded integer")
+impl ops::BitAnd for Uint256 {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Uint256(self.0 & rhs.0)
     }
+}
 
-    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
-    where
-        E: de::Error,
-    {
-        match U256::from_dec_str(v) {
-            Ok(u) => Ok(Uint256(u)),
-            Err(_e) => Err(E::custom(format!("invalid Uint256 '{}'", v))),
-        }
+impl ops::BitOr for Uint256 {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Uint256(self.0 | rhs.0)
     }
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use cosmwasm_std::{from_slice, to_vec, StdResult};
-    use std::convert::TryInto;
+impl ops::BitXor for Uint256 {
+    type Output = Self;
 
-    #[test]
-    fn decimal_one() {
-        let value = Decimal256::one();
-        assert_eq!(value.0, Decimal256::DECIMAL_FRACTIONAL);
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        Uint256(self.0 ^ rhs.0)
     }
+}
 
-    #[test]
-    fn decimal_zero() {
-        let value = Decimal256::zero();
-        assert_eq!(value.0, U256::zero());
+impl ops::SubAssign for Uint256 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
     }
+}
 
-    #[test]
-    fn decimal_percent() {
-        let value = Decimal256::percent(50);
-        assert_eq!(value.0, Decimal256::DECIMAL_FRACTIONAL / 2.into());
+impl ops::MulAssign for Uint256 {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
     }
+}
 
-    #[test]
-    fn decimal_permille() {
-        let value = Decimal256::permille(125);
-        assert_eq!(value.0, Decimal256::DECIMAL_FRACTIONAL / 8.into());
+impl<const DIGITS: u32> ops::MulAssign<FixedDecimal<DIGITS>> for Uint256 {
+    fn mul_assign(&mut self, rhs: FixedDecimal<DIGITS>) {
+        *self = *self * rhs;
     }
+}
 
-    #[test]
-    fn decimal_from_ratio_works() {
-        // 1.0
-        assert_eq!(Decimal256::from_ratio(1, 1), Decimal256::one());
-        assert_eq!(Decimal256::from_ratio(53, 53), Decimal256::one());
-        assert_eq!(Decimal256::from_ratio(125, 125), Decimal256::one());
+impl ops::DivAssign for Uint256 {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl<const DIGITS: u32> ops::DivAssign<FixedDecimal<DIGITS>> for Uint256 {
+    fn div_assign(&mut self, rhs: FixedDecimal<DIGITS>) {
+        *self = *self / rhs;
+    }
+}
+
+impl ops::RemAssign for Uint256 {
+    fn rem_assign(&mut self, rhs: Self) {
+        *self = *self % rhs;
+    }
+}
+
+impl ops::ShlAssign<u32> for Uint256 {
+    fn shl_assign(&mut self, rhs: u32) {
+        *self = *self << rhs;
+    }
+}
+
+impl ops::ShrAssign<u32> for Uint256 {
+    fn shr_assign(&mut self, rhs: u32) {
+        *self = *self >> rhs;
+    }
+}
+
+impl ops::BitAndAssign for Uint256 {
+    fn bitand_assign(&mut self, rhs: Self) {
+        *self = *self & rhs;
+    }
+}
+
+impl ops::BitOrAssign for Uint256 {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = *self | rhs;
+    }
+}
+
+impl ops::BitXorAssign for Uint256 {
+    fn bitxor_assign(&mut self, rhs: Self) {
+        *self = *self ^ rhs;
+    }
+}
+
+forward_ref_binop!(impl Add, add for Uint256, Uint256);
+forward_ref_binop!(impl Sub, sub for Uint256, Uint256);
+forward_ref_binop!(impl Mul, mul for Uint256, Uint256);
+forward_ref_binop!(impl[const DIGITS: u32] Mul, mul for Uint256, FixedDecimal<DIGITS>);
+forward_ref_binop!(impl[const DIGITS: u32] Mul, mul for FixedDecimal<DIGITS>, Uint256);
+forward_ref_binop!(impl Div, div for Uint256, Uint256);
+forward_ref_binop!(impl[const DIGITS: u32] Div, div for Uint256, FixedDecimal<DIGITS>);
+forward_ref_binop!(impl Rem, rem for Uint256, Uint256);
+forward_ref_binop!(impl Shl, shl for Uint256, u32);
+forward_ref_binop!(impl Shr, shr for Uint256, u32);
+forward_ref_binop!(impl BitAnd, bitand for Uint256, Uint256);
+forward_ref_binop!(impl BitOr, bitor for Uint256, Uint256);
+forward_ref_binop!(impl BitXor, bitxor for Uint256, Uint256);
+forward_ref_op_assign!(impl AddAssign, add_assign for Uint256, Uint256);
+forward_ref_op_assign!(impl SubAssign, sub_assign for Uint256, Uint256);
+forward_ref_op_assign!(impl MulAssign, mul_assign for Uint256, Uint256);
+forward_ref_op_assign!(impl[const DIGITS: u32] MulAssign, mul_assign for Uint256, FixedDecimal<DIGITS>);
+forward_ref_op_assign!(impl DivAssign, div_assign for Uint256, Uint256);
+forward_ref_op_assign!(impl[const DIGITS: u32] DivAssign, div_assign for Uint256, FixedDecimal<DIGITS>);
+forward_ref_op_assign!(impl RemAssign, rem_assign for Uint256, Uint256);
+forward_ref_op_assign!(impl ShlAssign, shl_assign for Uint256, u32);
+forward_ref_op_assign!(impl ShrAssign, shr_assign for Uint256, u32);
+forward_ref_op_assign!(impl BitAndAssign, bitand_assign for Uint256, Uint256);
+forward_ref_op_assign!(impl BitOrAssign, bitor_assign for Uint256, Uint256);
+forward_ref_op_assign!(impl BitXorAssign, bitxor_assign for Uint256, Uint256);
+
+impl<A: Borrow<Uint256>> iter::Sum<A> for Uint256 {
+    fn sum<I: Iterator<Item = A>>(iter: I) -> Self {
+        iter.fold(Uint256::zero(), |acc, x| acc + *x.borrow())
+    }
+}
+
+impl<A: Borrow<Uint256>> iter::Product<A> for Uint256 {
+    fn product<I: Iterator<Item = A>>(iter: I) -> Self {
+        iter.fold(Uint256::one(), |acc, x| acc * *x.borrow())
+    }
+}
+
+impl Uint256 {
+    /// returns self * nom / denom
+    pub fn multiply_ratio<A: Into<U256>, B: Into<U256>>(&self, nom: A, denom: B) -> Uint256 {
+        self.multiply_ratio_rounded(nom, denom, RoundingMode::Floor)
+    }
+
+    /// returns self * nom / denom, rounded according to `mode`
+    pub fn multiply_ratio_rounded<A: Into<U256>, B: Into<U256>>(
+        &self,
+        nom: A,
+        denom: B,
+        mode: RoundingMode,
+    ) -> Uint256 {
+        let nominator: U256 = nom.into();
+        let denominator: U256 = denom.into();
+        if denominator.is_zero() {
+            panic!("Denominator must not be zero");
+        }
+
+        // the product is computed in 512 bits, so only a result that exceeds Uint256 overflows
+        let val = div_rounded(self.0.full_mul(nominator), denominator.into(), mode);
+        match Uint256::try_from(Uint512(val)) {
+            Ok(val) => val,
+            Err(_) => panic!("Ratio overflows Uint256"),
+        }
+    }
+
+    /// returns self * rhs rounded down, same as `self * rhs`
+    pub fn mul_floor<const DIGITS: u32>(self, rhs: FixedDecimal<DIGITS>) -> Uint256 {
+        self.multiply_ratio_rounded(
+            rhs.0,
+            FixedDecimal::<DIGITS>::DECIMAL_FRACTIONAL,
+            RoundingMode::Floor,
+        )
+    }
+
+    /// returns self * rhs rounded up
+    pub fn mul_ceil<const DIGITS: u32>(self, rhs: FixedDecimal<DIGITS>) -> Uint256 {
+        self.multiply_ratio_rounded(
+            rhs.0,
+            FixedDecimal::<DIGITS>::DECIMAL_FRACTIONAL,
+            RoundingMode::Ceil,
+        )
+    }
+
+    /// returns self / rhs rounded down, same as `self / rhs`
+    pub fn div_floor<const DIGITS: u32>(self, rhs: FixedDecimal<DIGITS>) -> Uint256 {
+        self.multiply_ratio_rounded(
+            FixedDecimal::<DIGITS>::DECIMAL_FRACTIONAL,
+            rhs.0,
+            RoundingMode::Floor,
+        )
+    }
+
+    /// returns self / rhs rounded up
+    pub fn div_ceil<const DIGITS: u32>(self, rhs: FixedDecimal<DIGITS>) -> Uint256 {
+        self.multiply_ratio_rounded(
+            FixedDecimal::<DIGITS>::DECIMAL_FRACTIONAL,
+            rhs.0,
+            RoundingMode::Ceil,
+        )
+    }
+
+    /// Returns the exact product self * rhs
+    pub fn full_mul<A: Into<U256>>(&self, rhs: A) -> Uint512 {
+        Uint512(self.0.full_mul(rhs.into()))
+    }
+}
+
+/// Checked arithmetic that returns an error instead of panicking or wrapping
+impl Uint256 {
+    pub fn checked_add(self, other: Self) -> Result<Self, OverflowError> {
+        match self.0.overflowing_add(other.0) {
+            (val, false) => Ok(Uint256(val)),
+            (_, true) => Err(OverflowError::new(OverflowOperation::Add, self, other)),
+        }
+    }
+
+    pub fn checked_sub(self, other: Self) -> Result<Self, OverflowError> {
+        match self.0.overflowing_sub(other.0) {
+            (val, false) => Ok(Uint256(val)),
+            (_, true) => Err(OverflowError::new(OverflowOperation::Sub, self, other)),
+        }
+    }
+
+    pub fn checked_mul(self, other: Self) -> Result<Self, OverflowError> {
+        match self.0.overflowing_mul(other.0) {
+            (val, false) => Ok(Uint256(val)),
+            (_, true) => Err(OverflowError::new(OverflowOperation::Mul, self, other)),
+        }
+    }
+
+    pub fn checked_div(self, other: Self) -> Result<Self, DivideByZeroError> {
+        if other.is_zero() {
+            return Err(DivideByZeroError::new(self));
+        }
+        Ok(Uint256(self.0 / other.0))
+    }
+
+    pub fn checked_rem(self, other: Self) -> Result<Self, DivideByZeroError> {
+        if other.is_zero() {
+            return Err(DivideByZeroError::new(self));
+        }
+        Ok(Uint256(self.0 % other.0))
+    }
+
+    pub fn checked_pow(self, exp: u32) -> Result<Self, OverflowError> {
+        match self.0.overflowing_pow(exp.into()) {
+            (val, false) => Ok(Uint256(val)),
+            (_, true) => Err(OverflowError::new(
+                OverflowOperation::Pow,
+                self,
+                Uint256::from(exp as u64),
+            )),
+        }
+    }
+}
+
+impl Uint256 {
+    pub fn pow(self, exp: u32) -> Self {
+        match self.checked_pow(exp) {
+            Ok(val) => val,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Largest integer whose square does not exceed self
+    pub fn isqrt(&self) -> Uint256 {
+        Uint256(narrow_u512(isqrt_u512(self.0.into())).unwrap())
+    }
+}
+
+/// Serializes as a base64 string
+impl Serialize for Uint256 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// Deserializes as a base64 string
+impl<'de> Deserialize<'de> for Uint256 {
+    fn deserialize<D>(deserializer: D) -> Result<Uint256, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(Uint256Visitor)
+    }
+}
+
+struct Uint256Visitor;
+
+impl<'de> de::Visitor<'de> for Uint256Visitor {
+    type Value = Uint256;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("string-encoded integer")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match U256::from_dec_str(v) {
+            Ok(u) => Ok(Uint256(u)),
+            Err(_e) => Err(E::custom(format!("invalid Uint256 '{}'", v))),
+        }
+    }
+}
+
+//*** Uint512 ***/
+/// An unsigned 512-bit integer, mainly used as the intermediate of Uint256 products
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord, JsonSchema)]
+pub struct Uint512(#[schemars(with = "String")] pub U512);
+
+impl Uint512 {
+    /// Creates a Uint512(0)
+    pub const fn zero() -> Self {
+        Uint512(U512([0, 0, 0, 0, 0, 0, 0, 0]))
+    }
+
+    /// Creates a Uint512(1)
+    pub const fn one() -> Self {
+        Uint512(U512([1, 0, 0, 0, 0, 0, 0, 0]))
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+}
+
+impl From<U512> for Uint512 {
+    fn from(val: U512) -> Self {
+        Uint512(val)
+    }
+}
+
+impl From<U256> for Uint512 {
+    fn from(val: U256) -> Self {
+        Uint512(val.into())
+    }
+}
+
+impl From<Uint256> for Uint512 {
+    fn from(val: Uint256) -> Self {
+        Uint512(val.0.into())
+    }
+}
+
+impl From<u128> for Uint512 {
+    fn from(val: u128) -> Self {
+        Uint512::from(Uint256::from(val))
+    }
+}
+
+impl From<u64> for Uint512 {
+    fn from(val: u64) -> Self {
+        Uint512(val.into())
+    }
+}
+
+impl TryFrom<Uint512> for Uint256 {
+    type Error = ConversionOverflowError;
+
+    fn try_from(val: Uint512) -> Result<Self, Self::Error> {
+        narrow_u512(val.0)
+            .map(Uint256)
+            .ok_or_else(|| ConversionOverflowError::new("Uint512", "Uint256", val.to_string()))
+    }
+}
+
+impl FromStr for Uint512 {
+    type Err = StdError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let number =
+            U512::from_dec_str(input).map_err(|e| parse_error(e, "number", input, "Uint512"))?;
+        Ok(Uint512(number))
+    }
+}
+
+impl fmt::Display for Uint512 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl ops::Add for Uint512 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Uint512(self.0 + rhs.0)
+    }
+}
+
+impl ops::Sub for Uint512 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        assert!(self.0 >= rhs.0);
+        Uint512(self.0 - rhs.0)
+    }
+}
+
+impl ops::Mul for Uint512 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Uint512(self.0 * rhs.0)
+    }
+}
+
+impl ops::Div for Uint512 {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        assert!(!rhs.is_zero());
+        Uint512(self.0 / rhs.0)
+    }
+}
+
+impl ops::Rem for Uint512 {
+    type Output = Self;
+
+    fn rem(self, rhs: Self) -> Self {
+        assert!(!rhs.is_zero());
+        Uint512(self.0 % rhs.0)
+    }
+}
+
+/// Serializes as a decimal string
+impl Serialize for Uint512 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// Deserializes as a decimal string
+impl<'de> Deserialize<'de> for Uint512 {
+    fn deserialize<D>(deserializer: D) -> Result<Uint512, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(Uint512Visitor)
+    }
+}
+
+struct Uint512Visitor;
+
+impl<'de> de::Visitor<'de> for Uint512Visitor {
+    type Value = Uint512;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("string-encoded integer")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match U512::from_dec_str(v) {
+            Ok(u) => Ok(Uint512(u)),
+            Err(_e) => Err(E::custom(format!("invalid Uint512 '{}'", v))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cosmwasm_std::{from_slice, to_vec, StdResult};
+    use std::convert::TryInto;
+
+    #[test]
+    fn decimal_one() {
+        let value = Decimal256::one();
+        assert_eq!(value.0, Decimal256::DECIMAL_FRACTIONAL);
+    }
+
+    #[test]
+    fn decimal_zero() {
+        let value = Decimal256::zero();
+        assert_eq!(value.0, U256::zero());
+    }
+
+    #[test]
+    fn decimal_percent() {
+        let value = Decimal256::percent(50);
+        assert_eq!(value.0, Decimal256::DECIMAL_FRACTIONAL / 2.into());
+    }
+
+    #[test]
+    fn decimal_permille() {
+        let value = Decimal256::permille(125);
+        assert_eq!(value.0, Decimal256::DECIMAL_FRACTIONAL / 8.into());
+    }
+
+    #[test]
+    fn decimal_from_ratio_works() {
+        // 1.0
+        assert_eq!(Decimal256::from_ratio(1, 1), Decimal256::one());
+        assert_eq!(Decimal256::from_ratio(53, 53), Decimal256::one());
+        assert_eq!(Decimal256::from_ratio(125, 125), Decimal256::one());
 
         // 1.5
         assert_eq!(Decimal256::from_ratio(3, 2), Decimal256::percent(150));
@@ -499,13 +1582,13 @@ mod test {
         // 1/3 (result floored)
         assert_eq!(
             Decimal256::from_ratio(1, 3),
-            Decimal256(333_333_333_333_333_333u64.into())
+            Decimal256::new(333_333_333_333_333_333u64.into())
         );
 
         // 2/3 (result floored)
         assert_eq!(
             Decimal256::from_ratio(2, 3),
-            Decimal256(666_666_666_666_666_666u64.into())
+            Decimal256::new(666_666_666_666_666_666u64.into())
         );
     }
 
@@ -515,6 +1598,25 @@ mod test {
         Decimal256::from_ratio(1, 0);
     }
 
+    #[test]
+    fn decimal_from_ratio_is_exact_for_large_values() {
+        let a = U256::from(2u64).pow(190u64.into());
+        assert_eq!(
+            Decimal256::from_ratio(a, 3u64).to_string(),
+            "523091811282223396986315785267305534675196287038669542741.333333333333333333"
+        );
+        assert_eq!(
+            Decimal256::from_ratio(U256::MAX, Decimal256::DECIMAL_FRACTIONAL),
+            Decimal256::new(U256::MAX)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Ratio overflows Decimal256")]
+    fn decimal_from_ratio_panics_for_overflow() {
+        Decimal256::from_ratio(U256::MAX, 1u64);
+    }
+
     #[test]
     fn decimal_from_str_works() {
         // Integers
@@ -582,11 +1684,11 @@ mod test {
         // Can handle 18 fractional digits
         assert_eq!(
             Decimal256::from_str("7.123456789012345678").unwrap(),
-            Decimal256(7123456789012345678u64.into())
+            Decimal256::new(7123456789012345678u64.into())
         );
         assert_eq!(
             Decimal256::from_str("7.999999999999999999").unwrap(),
-            Decimal256(7999999999999999999u64.into())
+            Decimal256::new(7999999999999999999u64.into())
         );
 
         // Works for documented max value
@@ -662,24 +1764,35 @@ mod test {
     }
 
     #[test]
-    #[should_panic(expected = "arithmetic operation overflow")]
-    fn decimal_from_str_errors_for_more_than_max_value_integer_part() {
-        let _ =
-            Decimal256::from_str("115792089237316195423570985008687907853269984665640564039458");
-    }
-
-    #[test]
-    #[should_panic(expected = "arithmetic operation overflow")]
-    fn decimal_from_str_errors_for_more_than_max_value_integer_part_with_decimal() {
-        let _ =
-            Decimal256::from_str("115792089237316195423570985008687907853269984665640564039458.0");
-    }
-    #[test]
-    #[should_panic(expected = "arithmetic operation overflow")]
-    fn decimal_from_str_errors_for_more_than_max_value_decimal_part() {
-        let _ = Decimal256::from_str(
+    fn decimal_from_str_errors_for_more_than_max_value() {
+        for input in [
+            // integer part
+            "115792089237316195423570985008687907853269984665640564039458",
+            "115792089237316195423570985008687907853269984665640564039458.0",
+            // decimal part
             "115792089237316195423570985008687907853269984665640564039457.584007913129639936",
+            // integer part beyond U256 itself
+            &format!("1{}", "0".repeat(80)),
+        ] {
+            match Decimal256::from_str(input).unwrap_err() {
+                StdError::GenericErr { msg, .. } => {
+                    assert_eq!(msg, format!("Value {} overflows Decimal256", input))
+                }
+                e => panic!("Unexpected error: {:?}", e),
+            }
+        }
+        assert_eq!(
+            Decimal256::from_str(
+                "115792089237316195423570985008687907853269984665640564039457.584007913129639935"
+            )
+            .unwrap(),
+            Decimal256::MAX
         );
+
+        // deserializing user input returns an error instead of aborting
+        let json = format!(r#""1{}""#, "0".repeat(60));
+        let err = from_slice::<Decimal256>(json.as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("overflows Decimal256"), "{}", err);
     }
 
     #[test]
@@ -703,27 +1816,385 @@ mod test {
     }
 
     #[test]
-    fn decimal_sub() {
+    fn decimal_sub() {
+        assert_eq!(
+            Decimal256::percent(50),
+            Decimal256::one() - Decimal256::percent(50)
+        );
+    }
+
+    #[test]
+    fn decimal_mul() {
+        assert_eq!(
+            Decimal256::percent(25),
+            Decimal256::percent(50) * Decimal256::percent(50)
+        );
+    }
+
+    #[test]
+    fn decimal_div() {
+        assert_eq!(
+            Decimal256::one() + Decimal256::one(),
+            Decimal256::percent(50) / Decimal256::percent(25)
+        );
+    }
+
+    #[test]
+    fn decimal_checked_math() {
+        let max = Decimal256::MAX;
+        let one = Decimal256::one();
+        let half = Decimal256::percent(50);
+
+        assert_eq!(one.checked_add(half), Ok(Decimal256::percent(150)));
+        assert_eq!(
+            max.checked_add(Decimal256::new(U256::one())),
+            Err(OverflowError::new(
+                OverflowOperation::Add,
+                max,
+                Decimal256::new(U256::one())
+            ))
+        );
+
+        assert_eq!(one.checked_sub(half), Ok(half));
+        assert_eq!(
+            half.checked_sub(one),
+            Err(OverflowError::new(OverflowOperation::Sub, half, one))
+        );
+
+        // products far below MAX no longer overflow the intermediate value
+        let big = Decimal256::from_uint256(Uint256::from(10u64).checked_pow(40).unwrap());
+        assert_eq!(
+            big.checked_mul(Decimal256::percent(1)),
+            Ok(Decimal256::from_uint256(
+                Uint256::from(10u64).checked_pow(38).unwrap()
+            ))
+        );
+        assert_eq!(
+            big * Decimal256::percent(1),
+            big.checked_mul(Decimal256::percent(1)).unwrap()
+        );
+        assert_eq!(max.checked_mul(one), Ok(max));
+        assert_eq!(max.checked_mul(half), Ok(Decimal256::new(U256::MAX >> 1)));
+        assert_eq!(
+            max.checked_mul(Decimal256::percent(200)),
+            Err(OverflowError::new(
+                OverflowOperation::Mul,
+                max,
+                Decimal256::percent(200)
+            ))
+        );
+
+        assert_eq!(
+            big.checked_div(Decimal256::percent(200)).unwrap(),
+            big * half
+        );
+        assert_eq!(max.checked_div(one).unwrap(), max);
+        assert_eq!(
+            max.checked_div(Decimal256::percent(200)).unwrap(),
+            max * half
+        );
+        assert_eq!(
+            max.checked_div(half),
+            Err(CheckedFromRatioError::Overflow {
+                target_type: "Decimal256",
+                dividend: max.to_string(),
+                divisor: half.to_string(),
+            })
+        );
+        assert_eq!(
+            one.checked_div(Decimal256::zero()),
+            Err(CheckedFromRatioError::DivideByZero(DivideByZeroError::new(
+                one
+            )))
+        );
+        // converts into StdError for contracts returning StdResult
+        match StdError::from(max.checked_div(half).unwrap_err()) {
+            StdError::GenericErr { msg, .. } => {
+                assert!(msg.starts_with("Decimal256 division overflow"))
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+        match StdError::from(one.checked_div(Decimal256::zero()).unwrap_err()) {
+            StdError::DivideByZero { .. } => {}
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn decimal_saturating_math() {
+        let max = Decimal256::MAX;
+        let one = Decimal256::one();
+        let half = Decimal256::percent(50);
+
+        assert_eq!(one.saturating_add(half), Decimal256::percent(150));
+        assert_eq!(max.saturating_add(one), max);
+        assert_eq!(one.saturating_sub(half), half);
+        assert_eq!(half.saturating_sub(one), Decimal256::zero());
+        assert_eq!(half.saturating_mul(half), Decimal256::percent(25));
+        assert_eq!(max.saturating_mul(Decimal256::percent(101)), max);
+        assert_eq!(one.saturating_div(half), Decimal256::percent(200));
+        assert_eq!(max.saturating_div(half), max);
+        assert_eq!(one.saturating_div(Decimal256::zero()), max);
+    }
+
+    #[test]
+    fn decimal_saturating_div_zero_by_zero() {
+        assert_eq!(
+            Decimal256::zero().saturating_div(Decimal256::zero()),
+            Decimal256::MAX
+        );
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn decimal_operators() {
+        let a = Decimal256::percent(150);
+        let b = Decimal256::percent(40);
+
+        assert_eq!(&a + &b, Decimal256::percent(190));
+        assert_eq!(a - &b, Decimal256::percent(110));
+        assert_eq!(&a * b, Decimal256::percent(60));
+        assert_eq!(&a / &b, Decimal256::from_ratio(15u64, 4u64));
+        assert_eq!(a % b, Decimal256::percent(30));
+        assert_eq!(&a % &b, Decimal256::percent(30));
+
+        let mut c = Decimal256::percent(150);
+        c -= &Decimal256::percent(50);
+        assert_eq!(c, Decimal256::one());
+        c *= Decimal256::percent(250);
+        assert_eq!(c, Decimal256::percent(250));
+        c /= &Decimal256::percent(50);
+        assert_eq!(c, Decimal256::percent(500));
+        c %= Decimal256::percent(300);
+        assert_eq!(c, Decimal256::percent(200));
+        c += &Decimal256::percent(5);
+        assert_eq!(c, Decimal256::percent(205));
+    }
+
+    #[test]
+    fn decimal_sum_and_product() {
+        let nums = vec![
+            Decimal256::percent(50),
+            Decimal256::percent(150),
+            Decimal256::percent(200),
+        ];
+        assert_eq!(nums.iter().sum::<Decimal256>(), Decimal256::percent(400));
+        assert_eq!(
+            nums.iter().product::<Decimal256>(),
+            Decimal256::percent(150)
+        );
+        assert_eq!(
+            nums.into_iter().product::<Decimal256>(),
+            Decimal256::percent(150)
+        );
+
+        let empty: Vec<Decimal256> = vec![];
+        assert_eq!(empty.iter().sum::<Decimal256>(), Decimal256::zero());
+        assert_eq!(empty.iter().product::<Decimal256>(), Decimal256::one());
+    }
+
+    #[test]
+    fn decimal_rounding() {
+        let dec = |s: &str| Decimal256::from_str(s).unwrap();
+
+        assert_eq!(dec("1.5").floor(), dec("1"));
+        assert_eq!(dec("1.5").ceil(), dec("2"));
+        assert_eq!(dec("1.5").round(0), dec("2"));
+        assert_eq!(dec("1.4999").round(0), dec("1"));
+        assert_eq!(dec("3").floor(), dec("3"));
+        assert_eq!(dec("3").ceil(), dec("3"));
+        assert_eq!(Decimal256::zero().ceil(), Decimal256::zero());
+        assert_eq!(dec("0.000000000000000001").ceil(), Decimal256::one());
+
+        assert_eq!(dec("1.2345").round(2), dec("1.23"));
+        assert_eq!(dec("1.235").round(2), dec("1.24"));
+        assert_eq!(dec("1.2345").round(18), dec("1.2345"));
+        assert_eq!(dec("1.2345").round(30), dec("1.2345"));
+        assert_eq!(dec("1.2301").round_with(2, RoundingMode::Ceil), dec("1.24"));
+        assert_eq!(
+            dec("1.2399").round_with(2, RoundingMode::Floor),
+            dec("1.23")
+        );
+        assert_eq!(dec("2.5").round_with(0, RoundingMode::HalfEven), dec("2"));
+        assert_eq!(dec("3.5").round_with(0, RoundingMode::HalfEven), dec("4"));
+        assert_eq!(
+            dec("1.245").round_with(2, RoundingMode::HalfEven),
+            dec("1.24")
+        );
+        assert_eq!(
+            dec("1.2451").round_with(2, RoundingMode::HalfEven),
+            dec("1.25")
+        );
+
+        assert_eq!(dec("1.5").to_uint_floor(), Uint256::from(1u64));
+        assert_eq!(dec("1.5").to_uint_ceil(), Uint256::from(2u64));
+        assert_eq!(dec("2").to_uint_floor(), Uint256::from(2u64));
+        assert_eq!(dec("2").to_uint_ceil(), Uint256::from(2u64));
+        assert_eq!(
+            dec("2.5").to_uint(RoundingMode::HalfUp),
+            Uint256::from(3u64)
+        );
+        assert_eq!(
+            Decimal256::MAX.to_uint_ceil(),
+            Decimal256::MAX.to_uint_floor() + Uint256::one()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Rounding overflows Decimal256")]
+    fn decimal_ceil_panics_for_overflow() {
+        Decimal256::MAX.ceil();
+    }
+
+    /// Asserts |actual - expected| <= expected * 10^-digits + 10^-18
+    fn assert_close(actual: Decimal256, expected: Decimal256, digits: u32) {
+        let diff = if actual > expected {
+            actual - expected
+        } else {
+            expected - actual
+        };
+        let tolerance = U512::from(expected.0) + U512::from(10u64).pow(digits.into());
+        assert!(
+            diff.0.full_mul(U256::from(10u64).pow(digits.into())) <= tolerance,
+            "{} is not within 10^-{} of {}",
+            actual,
+            digits,
+            expected
+        );
+    }
+
+    #[test]
+    fn decimal_pow() {
+        let dec = |s: &str| Decimal256::from_str(s).unwrap();
+
+        assert_eq!(dec("1.1").pow(0), Decimal256::one());
+        assert_eq!(dec("1.1").pow(1), dec("1.1"));
+        assert_eq!(dec("1.1").pow(10), dec("2.5937424601"));
+        assert_eq!(Decimal256::zero().pow(5), Decimal256::zero());
+        assert_close(dec("1.0001").pow(1000), dec("1.105165392603232697"), 15);
+        assert_close(dec("0.9").pow(50), dec("0.005153775207320113"), 14);
+        assert_close(
+            dec("2.5").pow(30),
+            dec("867361737988.403547205962240696"),
+            17,
+        );
+
+        assert!(Decimal256::MAX.checked_pow(1).is_ok());
+        assert_eq!(
+            dec("10").checked_pow(60),
+            Err(OverflowError::new(
+                OverflowOperation::Pow,
+                "10".to_string(),
+                "60".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn decimal_sqrt() {
+        let dec = |s: &str| Decimal256::from_str(s).unwrap();
+
+        assert_eq!(Decimal256::zero().sqrt(), Decimal256::zero());
+        assert_eq!(Decimal256::one().sqrt(), Decimal256::one());
+        assert_eq!(dec("2.25").sqrt(), dec("1.5"));
+        assert_eq!(dec("2").sqrt(), dec("1.414213562373095048"));
+        assert_eq!(dec("0.000000000000000001").sqrt(), dec("0.000000001"));
+        assert_eq!(
+            Decimal256::MAX.sqrt(),
+            dec("340282366920938463463374607431.768211455999999999")
+        );
+
+        // the result is the largest value whose square does not exceed the input
+        let mut x = U256::from(7u64);
+        while x < U256::MAX / U256::from(13u64) {
+            let root = Decimal256::new(x).sqrt().0;
+            let scaled = x.full_mul(Decimal256::DECIMAL_FRACTIONAL);
+            assert!(root.full_mul(root) <= scaled);
+            let next = root + U256::one();
+            assert!(next.full_mul(next) > scaled);
+            x = x * U256::from(13u64) + U256::from(5u64);
+        }
+    }
+
+    #[test]
+    fn decimal_exp() {
+        let dec = |s: &str| Decimal256::from_str(s).unwrap();
+
+        assert_eq!(Decimal256::zero().exp().unwrap(), Decimal256::one());
+        assert_eq!(
+            dec("0.000000000000000001").exp().unwrap(),
+            dec("1.000000000000000001")
+        );
+        assert_eq!(dec("0.5").exp().unwrap(), dec("1.648721270700128147"));
+        assert_eq!(
+            Decimal256::one().exp().unwrap(),
+            dec("2.718281828459045235")
+        );
+        assert_eq!(
+            dec("2.302585092994045684").exp().unwrap(),
+            dec("10.000000000000000000")
+        );
+        assert_eq!(dec("10").exp().unwrap(), dec("22026.465794806716516958"));
+        assert_close(
+            dec("42.123456789").exp().unwrap(),
+            dec("1967817607279494810.156306567758136119"),
+            30,
+        );
+        assert_close(
+            dec("100").exp().unwrap(),
+            dec("26881171418161354484126255515800135873611118.773741922415191609"),
+            30,
+        );
+        assert_close(
+            dec("135.999146549453176898").exp().unwrap(),
+            dec("115792089237316195423570985008687907853269984665640564039457.584007913129639935"),
+            17,
+        );
+
+        // e^136 > Decimal256::MAX
+        match dec("136").exp().unwrap_err() {
+            StdError::GenericErr { msg, .. } => assert_eq!(msg, "exp(136) overflows Decimal256"),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+        assert!(Decimal256::MAX.exp().is_err());
+    }
+
+    #[test]
+    fn decimal_ln() {
+        let dec = |s: &str| Decimal256::from_str(s).unwrap();
+
+        assert_eq!(Decimal256::one().ln().unwrap(), Decimal256::zero());
         assert_eq!(
-            Decimal256::percent(50),
-            Decimal256::one() - Decimal256::percent(50)
+            dec("1.000000000000000001").ln().unwrap(),
+            dec("0.000000000000000001")
         );
-    }
-
-    #[test]
-    fn decimal_mul() {
+        assert_eq!(dec("1.5").ln().unwrap(), dec("0.405465108108164382"));
+        assert_eq!(dec("2").ln().unwrap(), dec("0.693147180559945309"));
+        assert_eq!(dec("10").ln().unwrap(), dec("2.302585092994045684"));
         assert_eq!(
-            Decimal256::percent(25),
-            Decimal256::percent(50) * Decimal256::percent(50)
+            dec("123456.789").ln().unwrap(),
+            dec("11.723646487185880981")
         );
-    }
-
-    #[test]
-    fn decimal_div() {
         assert_eq!(
-            Decimal256::one() + Decimal256::one(),
-            Decimal256::percent(50) / Decimal256::percent(25)
+            dec("1000000000000000000").ln().unwrap(),
+            dec("41.446531673892822312")
         );
+        assert_eq!(Decimal256::MAX.ln().unwrap(), dec("135.999146549453176898"));
+
+        match dec("0.5").ln().unwrap_err() {
+            StdError::GenericErr { msg, .. } => assert_eq!(
+                msg,
+                "ln(0.5) is negative and cannot be represented as Decimal256"
+            ),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+
+        // exp and ln are inverse to each other up to the rounding of the logarithm
+        let mut x = Decimal256::one();
+        while x < dec("1000000000000000000000000000000") {
+            assert_close(x.ln().unwrap().exp().unwrap(), x, 17);
+            x = x * dec("3.7") + dec("0.000000000000123");
+        }
     }
 
     #[test]
@@ -739,23 +2210,74 @@ mod test {
         assert_eq!(Decimal256::percent(1).to_string(), "0.01");
         assert_eq!(Decimal256::permille(987).to_string(), "0.987");
 
-        assert_eq!(Decimal256(1u64.into()).to_string(), "0.000000000000000001");
-        assert_eq!(Decimal256(10u64.into()).to_string(), "0.00000000000000001");
-        assert_eq!(Decimal256(100u64.into()).to_string(), "0.0000000000000001");
-        assert_eq!(Decimal256(1000u64.into()).to_string(), "0.000000000000001");
-        assert_eq!(Decimal256(10000u64.into()).to_string(), "0.00000000000001");
-        assert_eq!(Decimal256(100000u64.into()).to_string(), "0.0000000000001");
-        assert_eq!(Decimal256(1000000u64.into()).to_string(), "0.000000000001");
-        assert_eq!(Decimal256(10000000u64.into()).to_string(), "0.00000000001");
-        assert_eq!(Decimal256(100000000u64.into()).to_string(), "0.0000000001");
-        assert_eq!(Decimal256(1000000000u64.into()).to_string(), "0.000000001");
-        assert_eq!(Decimal256(10000000000u64.into()).to_string(), "0.00000001");
-        assert_eq!(Decimal256(100000000000u64.into()).to_string(), "0.0000001");
-        assert_eq!(Decimal256(10000000000000u64.into()).to_string(), "0.00001");
-        assert_eq!(Decimal256(100000000000000u64.into()).to_string(), "0.0001");
-        assert_eq!(Decimal256(1000000000000000u64.into()).to_string(), "0.001");
-        assert_eq!(Decimal256(10000000000000000u64.into()).to_string(), "0.01");
-        assert_eq!(Decimal256(100000000000000000u64.into()).to_string(), "0.1");
+        assert_eq!(
+            Decimal256::new(1u64.into()).to_string(),
+            "0.000000000000000001"
+        );
+        assert_eq!(
+            Decimal256::new(10u64.into()).to_string(),
+            "0.00000000000000001"
+        );
+        assert_eq!(
+            Decimal256::new(100u64.into()).to_string(),
+            "0.0000000000000001"
+        );
+        assert_eq!(
+            Decimal256::new(1000u64.into()).to_string(),
+            "0.000000000000001"
+        );
+        assert_eq!(
+            Decimal256::new(10000u64.into()).to_string(),
+            "0.00000000000001"
+        );
+        assert_eq!(
+            Decimal256::new(100000u64.into()).to_string(),
+            "0.0000000000001"
+        );
+        assert_eq!(
+            Decimal256::new(1000000u64.into()).to_string(),
+            "0.000000000001"
+        );
+        assert_eq!(
+            Decimal256::new(10000000u64.into()).to_string(),
+            "0.00000000001"
+        );
+        assert_eq!(
+            Decimal256::new(100000000u64.into()).to_string(),
+            "0.0000000001"
+        );
+        assert_eq!(
+            Decimal256::new(1000000000u64.into()).to_string(),
+            "0.000000001"
+        );
+        assert_eq!(
+            Decimal256::new(10000000000u64.into()).to_string(),
+            "0.00000001"
+        );
+        assert_eq!(
+            Decimal256::new(100000000000u64.into()).to_string(),
+            "0.0000001"
+        );
+        assert_eq!(
+            Decimal256::new(10000000000000u64.into()).to_string(),
+            "0.00001"
+        );
+        assert_eq!(
+            Decimal256::new(100000000000000u64.into()).to_string(),
+            "0.0001"
+        );
+        assert_eq!(
+            Decimal256::new(1000000000000000u64.into()).to_string(),
+            "0.001"
+        );
+        assert_eq!(
+            Decimal256::new(10000000000000000u64.into()).to_string(),
+            "0.01"
+        );
+        assert_eq!(
+            Decimal256::new(100000000000000000u64.into()).to_string(),
+            "0.1"
+        );
     }
 
     #[test]
@@ -805,6 +2327,93 @@ mod test {
         );
     }
 
+    type Decimal6 = FixedDecimal<6>;
+
+    #[test]
+    fn fixed_decimal_six_digits() {
+        assert_eq!(Decimal6::DECIMAL_FRACTIONAL, U256::from(1_000_000u64));
+        assert_eq!(Decimal6::one(), Decimal6::new(1_000_000u64.into()));
+        assert_eq!(Decimal6::percent(125), Decimal6::from_str("1.25").unwrap());
+        assert_eq!(Decimal6::from_ratio(1u64, 3u64).to_string(), "0.333333");
+        assert_eq!(Decimal6::from_str("0.000001").unwrap().0, U256::one());
+        match Decimal6::from_str("0.0000001").unwrap_err() {
+            StdError::GenericErr { msg, .. } => {
+                assert_eq!(msg, "Cannot parse more than 6 fractional digits")
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+
+        let price = Decimal6::from_str("1.5").unwrap();
+        assert_eq!(
+            price * Decimal6::percent(50),
+            Decimal6::from_str("0.75").unwrap()
+        );
+        assert_eq!(
+            price / Decimal6::from_str("0.5").unwrap(),
+            Decimal6::from_ratio(3u64, 1u64)
+        );
+        assert_eq!(
+            Decimal6::from_str("2").unwrap().sqrt(),
+            Decimal6::from_str("1.414213").unwrap()
+        );
+        assert_eq!(Uint256::from(1000u64) * price, Uint256::from(1500u64));
+        assert_eq!(
+            Uint256::from(1000u64).div_ceil(Decimal6::from_ratio(1u64, 3u64)),
+            Uint256::from(3001u64)
+        );
+
+        assert_eq!(to_vec(&price).unwrap(), br#""1.5""#);
+        assert_eq!(from_slice::<Decimal6>(br#""1.5""#).unwrap(), price);
+        assert!(from_slice::<Decimal6>(br#""1.0000001""#).is_err());
+
+        assert_eq!(Decimal6::schema_name(), "FixedDecimal6");
+        assert_eq!(FixedDecimal::<0>::schema_name(), "FixedDecimal0");
+        assert_eq!(FixedDecimal::<38>::schema_name(), "FixedDecimal38");
+        assert_eq!(Decimal256::schema_name(), "Decimal256");
+    }
+
+    #[test]
+    fn fixed_decimal_rescale() {
+        let value = Decimal6::from_str("1234.567891").unwrap();
+        let wide: Decimal256 = value.checked_rescale().unwrap();
+        assert_eq!(wide, Decimal256::from_str("1234.567891").unwrap());
+        assert_eq!(wide.checked_rescale::<6>().unwrap(), value);
+
+        let precise = Decimal256::from_str("0.1234565").unwrap();
+        match precise.checked_rescale::<6>().unwrap_err() {
+            StdError::GenericErr { msg, .. } => {
+                assert_eq!(msg, "0.1234565 has more than 6 fractional digits")
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+        assert_eq!(
+            precise.rescale::<6>(RoundingMode::Floor).unwrap(),
+            Decimal6::from_str("0.123456").unwrap()
+        );
+        assert_eq!(
+            precise.rescale::<6>(RoundingMode::HalfEven).unwrap(),
+            Decimal6::from_str("0.123456").unwrap()
+        );
+        assert_eq!(
+            precise.rescale::<6>(RoundingMode::HalfUp).unwrap(),
+            Decimal6::from_str("0.123457").unwrap()
+        );
+
+        assert_eq!(
+            Decimal6::MAX
+                .rescale::<18>(RoundingMode::Floor)
+                .unwrap_err(),
+            ConversionOverflowError::new("FixedDecimal6", "Decimal256", Decimal6::MAX.to_string())
+        );
+        assert_eq!(
+            Decimal256::MAX
+                .rescale::<6>(RoundingMode::Ceil)
+                .unwrap()
+                .to_string(),
+            "115792089237316195423570985008687907853269984665640564039457.584008"
+        );
+    }
+
     #[test]
     fn to_and_from_uint256() {
         let a: Uint256 = 12345u64.into();
@@ -861,6 +2470,95 @@ mod test {
         c += b;
         assert_eq!(c, Uint256::from(323456u64));
     }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn uint256_operators() {
+        let a = Uint256::from(23456u64);
+        let b = Uint256::from(12345u64);
+
+        assert_eq!(a / b, Uint256::one());
+        assert_eq!(a % b, Uint256::from(11111u64));
+        assert_eq!(&a + &b, Uint256::from(35801u64));
+        assert_eq!(a - &b, Uint256::from(11111u64));
+        assert_eq!(&a * b, Uint256::from(289564320u64));
+        assert_eq!(&a / &b, a / b);
+        assert_eq!(&a % b, a % b);
+        assert_eq!(&a * &Decimal256::percent(50), Uint256::from(11728u64));
+        assert_eq!(&Decimal256::percent(50) * a, Uint256::from(11728u64));
+        assert_eq!(a / &Decimal256::percent(50), Uint256::from(46912u64));
+
+        assert_eq!(Uint256::one() << 8, Uint256::from(256u64));
+        assert_eq!(&Uint256::from(256u64) >> &8, Uint256::one());
+        assert_eq!(Uint256::one() << 255, Uint256(U256::one() << 255));
+        assert_eq!(
+            Uint256::from(0b1100u64) & Uint256::from(0b1010u64),
+            Uint256::from(0b1000u64)
+        );
+        assert_eq!(
+            Uint256::from(0b1100u64) | &Uint256::from(0b1010u64),
+            Uint256::from(0b1110u64)
+        );
+        assert_eq!(
+            &Uint256::from(0b1100u64) ^ Uint256::from(0b1010u64),
+            Uint256::from(0b0110u64)
+        );
+
+        let mut c = Uint256::from(100u64);
+        c -= Uint256::from(10u64);
+        assert_eq!(c, Uint256::from(90u64));
+        c *= &Uint256::from(3u64);
+        assert_eq!(c, Uint256::from(270u64));
+        c /= Uint256::from(4u64);
+        assert_eq!(c, Uint256::from(67u64));
+        c %= &Uint256::from(10u64);
+        assert_eq!(c, Uint256::from(7u64));
+        c *= Decimal256::percent(200);
+        assert_eq!(c, Uint256::from(14u64));
+        c /= &Decimal256::percent(50);
+        assert_eq!(c, Uint256::from(28u64));
+        c <<= 2;
+        assert_eq!(c, Uint256::from(112u64));
+        c >>= &3;
+        assert_eq!(c, Uint256::from(14u64));
+        c &= Uint256::from(0b0110u64);
+        assert_eq!(c, Uint256::from(0b0110u64));
+        c |= Uint256::from(0b1001u64);
+        assert_eq!(c, Uint256::from(0b1111u64));
+        c ^= &Uint256::from(0b0101u64);
+        assert_eq!(c, Uint256::from(0b1010u64));
+        c += &Uint256::one();
+        assert_eq!(c, Uint256::from(11u64));
+    }
+
+    #[test]
+    fn uint256_sum_and_product() {
+        let nums = vec![
+            Uint256::from(2u64),
+            Uint256::from(3u64),
+            Uint256::from(7u64),
+        ];
+        assert_eq!(nums.iter().sum::<Uint256>(), Uint256::from(12u64));
+        assert_eq!(nums.iter().product::<Uint256>(), Uint256::from(42u64));
+        assert_eq!(nums.into_iter().sum::<Uint256>(), Uint256::from(12u64));
+
+        let empty: Vec<Uint256> = vec![];
+        assert_eq!(empty.iter().sum::<Uint256>(), Uint256::zero());
+        assert_eq!(empty.iter().product::<Uint256>(), Uint256::one());
+    }
+
+    #[test]
+    #[should_panic(expected = "by zero")]
+    fn uint256_div_panics_for_zero() {
+        let _ = Uint256::one() / Uint256::zero();
+    }
+
+    #[test]
+    #[should_panic(expected = "Shift of 256 bits overflows Uint256")]
+    fn uint256_shl_panics_for_overflow() {
+        let _ = Uint256::one() << 256;
+    }
+
     #[test]
     #[should_panic]
     fn uint256_math_sub_underflow() {
@@ -880,6 +2578,102 @@ mod test {
         let _ = almost_max + Uint256::from(12u64);
     }
 
+    #[test]
+    fn uint256_checked_math() {
+        let max = Uint256(U256::MAX);
+        let one = Uint256::one();
+
+        assert_eq!(
+            Uint256::from(12345u64).checked_add(Uint256::from(23456u64)),
+            Ok(Uint256::from(35801u64))
+        );
+        assert_eq!(max.checked_add(Uint256::zero()), Ok(max));
+        assert_eq!(
+            max.checked_add(one),
+            Err(OverflowError::new(OverflowOperation::Add, max, one))
+        );
+
+        assert_eq!(max.checked_sub(max), Ok(Uint256::zero()));
+        assert_eq!(
+            Uint256::zero().checked_sub(one),
+            Err(OverflowError::new(
+                OverflowOperation::Sub,
+                Uint256::zero(),
+                one
+            ))
+        );
+
+        assert_eq!(max.checked_mul(one), Ok(max));
+        assert_eq!(max.checked_mul(Uint256::zero()), Ok(Uint256::zero()));
+        let half = Uint256(U256::MAX >> 1) + one;
+        assert_eq!(
+            half.checked_mul(Uint256::from(2u64)),
+            Err(OverflowError::new(
+                OverflowOperation::Mul,
+                half,
+                Uint256::from(2u64)
+            ))
+        );
+        assert_eq!((half - one).checked_mul(Uint256::from(2u64)), Ok(max - one));
+
+        assert_eq!(max.checked_div(max), Ok(one));
+        assert_eq!(
+            Uint256::from(23456u64).checked_div(Uint256::from(12345u64)),
+            Ok(one)
+        );
+        assert_eq!(
+            max.checked_div(Uint256::zero()),
+            Err(DivideByZeroError::new(max))
+        );
+
+        assert_eq!(
+            Uint256::from(23456u64).checked_rem(Uint256::from(12345u64)),
+            Ok(Uint256::from(11111u64))
+        );
+        assert_eq!(max.checked_rem(half), Ok(half - one));
+        assert_eq!(
+            max.checked_rem(Uint256::zero()),
+            Err(DivideByZeroError::new(max))
+        );
+
+        let two = Uint256::from(2u64);
+        assert_eq!(two.checked_pow(0), Ok(one));
+        assert_eq!(two.checked_pow(255), Ok(half));
+        assert_eq!(
+            two.checked_pow(256),
+            Err(OverflowError::new(
+                OverflowOperation::Pow,
+                two,
+                Uint256::from(256u64)
+            ))
+        );
+        assert_eq!(max.checked_pow(1), Ok(max));
+        assert!(max.checked_pow(2).is_err());
+    }
+
+    #[test]
+    fn uint256_checked_math_into_std_error() {
+        let err: StdError = Uint256::zero()
+            .checked_sub(Uint256::one())
+            .unwrap_err()
+            .into();
+        match err {
+            StdError::Overflow { source, .. } => {
+                assert_eq!(source.operation, OverflowOperation::Sub)
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+
+        let err: StdError = Uint256::one()
+            .checked_div(Uint256::zero())
+            .unwrap_err()
+            .into();
+        match err {
+            StdError::DivideByZero { .. } => {}
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
     #[test]
     // in this test the Decimal256 is on the right
     fn uint256_decimal_multiply() {
@@ -921,6 +2715,187 @@ mod test {
         assert_eq!(base.multiply_ratio(100, 120), Uint256::from(416u64));
     }
 
+    #[test]
+    fn u256_multiply_ratio_is_exact_for_large_values() {
+        let max = Uint256(U256::MAX);
+
+        assert_eq!(max.multiply_ratio(max, max), max);
+        assert_eq!(
+            max.multiply_ratio(3u64, 7u64),
+            Uint256::from_str(
+                "49625181101706940895816136432294817651401421999560241731196107431962769845686"
+            )
+            .unwrap()
+        );
+        assert_eq!(max * Decimal256::percent(50), Uint256(U256::MAX >> 1));
+        assert_eq!(max / Decimal256::percent(200), Uint256(U256::MAX >> 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Ratio overflows Uint256")]
+    fn u256_multiply_ratio_panics_for_overflow() {
+        Uint256(U256::MAX).multiply_ratio(3u64, 2u64);
+    }
+
+    #[test]
+    fn u512_math() {
+        let max = Uint256(U256::MAX);
+        let product = max.full_mul(max);
+        assert_eq!(
+            product.to_string(),
+            "13407807929942597099574024998205846127479365820592393377723561443721764030073315392623399665776056285720014482370779510884422601683867654778417822746804225"
+        );
+        assert_eq!(product / Uint512::from(max), Uint512::from(max));
+        assert_eq!(product % Uint512::from(max), Uint512::zero());
+        assert_eq!(
+            Uint512::from(12345u64) + Uint512::from(23456u64),
+            Uint512::from(35801u64)
+        );
+        assert_eq!(
+            Uint512::from(23456u64) - Uint512::from(12345u64),
+            Uint512::from(11111u64)
+        );
+        assert_eq!(
+            Uint512::from(3u64) * Uint512::from(4u64),
+            Uint512::from(12u128)
+        );
+
+        assert_eq!(Uint256::try_from(Uint512::from(max)).unwrap(), max);
+        assert_eq!(
+            Uint256::try_from(product),
+            Err(ConversionOverflowError::new(
+                "Uint512",
+                "Uint256",
+                product.to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn u512_json() {
+        let orig = Uint256(U256::MAX).full_mul(2u64);
+        let serialized = to_vec(&orig).unwrap();
+        assert_eq!(serialized.as_slice(), format!("\"{}\"", orig).as_bytes());
+        let parsed: Uint512 = from_slice(&serialized).unwrap();
+        assert_eq!(parsed, orig);
+        assert_eq!(Uint512::from_str(&orig.to_string()).unwrap(), orig);
+    }
+
+    #[test]
+    fn uint_from_str_errors_for_more_than_max_value() {
+        let too_big = format!("1{}", "0".repeat(80));
+        match Uint256::from_str(&too_big).unwrap_err() {
+            StdError::GenericErr { msg, .. } => {
+                assert_eq!(msg, format!("Value {} overflows Uint256", too_big))
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+        let too_big = format!("1{}", "0".repeat(160));
+        match Uint512::from_str(&too_big).unwrap_err() {
+            StdError::GenericErr { msg, .. } => {
+                assert_eq!(msg, format!("Value {} overflows Uint512", too_big))
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+        match Uint256::from_str("12a").unwrap_err() {
+            StdError::GenericErr { msg, .. } => assert_eq!(msg, "Error parsing number"),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn uint256_rounding() {
+        let hundred = Uint256::from(100u64);
+
+        assert_eq!(
+            hundred.mul_floor(Decimal256::permille(333)),
+            Uint256::from(33u64)
+        );
+        assert_eq!(
+            hundred.mul_ceil(Decimal256::permille(333)),
+            Uint256::from(34u64)
+        );
+        assert_eq!(
+            hundred.mul_ceil(Decimal256::percent(150)),
+            Uint256::from(150u64)
+        );
+        assert_eq!(
+            hundred.mul_floor(Decimal256::permille(333)),
+            hundred * Decimal256::permille(333)
+        );
+
+        assert_eq!(
+            hundred.div_floor(Decimal256::percent(30)),
+            Uint256::from(333u64)
+        );
+        assert_eq!(
+            hundred.div_ceil(Decimal256::percent(30)),
+            Uint256::from(334u64)
+        );
+        assert_eq!(
+            hundred.div_ceil(Decimal256::percent(50)),
+            Uint256::from(200u64)
+        );
+        assert_eq!(
+            hundred.div_floor(Decimal256::percent(30)),
+            hundred / Decimal256::percent(30)
+        );
+
+        let five = Uint256::from(5u64);
+        assert_eq!(
+            five.multiply_ratio_rounded(1u64, 2u64, RoundingMode::HalfUp),
+            Uint256::from(3u64)
+        );
+        assert_eq!(
+            five.multiply_ratio_rounded(1u64, 2u64, RoundingMode::HalfEven),
+            Uint256::from(2u64)
+        );
+        assert_eq!(
+            Uint256::from(7u64).multiply_ratio_rounded(1u64, 2u64, RoundingMode::HalfEven),
+            Uint256::from(4u64)
+        );
+        assert_eq!(
+            five.multiply_ratio_rounded(1u64, 3u64, RoundingMode::HalfUp),
+            Uint256::from(2u64)
+        );
+
+        let max = Uint256(U256::MAX);
+        assert_eq!(
+            max.multiply_ratio_rounded(max, max, RoundingMode::Ceil),
+            max
+        );
+    }
+
+    #[test]
+    fn uint256_pow_and_isqrt() {
+        assert_eq!(Uint256::from(3u64).pow(4), Uint256::from(81u64));
+        assert_eq!(Uint256::from(2u64).pow(255), Uint256(U256::one() << 255));
+
+        assert_eq!(Uint256::zero().isqrt(), Uint256::zero());
+        assert_eq!(Uint256::one().isqrt(), Uint256::one());
+        assert_eq!(Uint256::from(80u64).isqrt(), Uint256::from(8u64));
+        assert_eq!(Uint256::from(81u64).isqrt(), Uint256::from(9u64));
+        assert_eq!(
+            Uint256(U256::MAX).isqrt(),
+            Uint256::from(340282366920938463463374607431768211455u128)
+        );
+
+        let mut n = U256::from(3u64);
+        while n < U256::MAX / U256::from(11u64) {
+            let root = Uint256(n).isqrt().0;
+            assert!(root.full_mul(root) <= n.into());
+            let next = root + U256::one();
+            assert!(next.full_mul(next) > n.into());
+            n = n * U256::from(11u64) + U256::from(7u64);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot Pow with 2 and 256")]
+    fn uint256_pow_panics_for_overflow() {
+        Uint256::from(2u64).pow(256);
+    }
+
     #[test]
     fn u256_from_u128() {
         assert_eq!(Uint256::from(100u64), Uint256::from(100u128));
@@ -942,16 +2917,59 @@ mod test {
 
     #[test]
     fn u256_into_u128() {
-        let val: u128 = Uint256::from(1234556700000000000999u128).into();
+        let val: u128 = Uint256::from(1234556700000000000999u128)
+            .try_into()
+            .unwrap();
         assert_eq!(val, 1234556700000000000999u128);
+
+        let val: Uint128 = Uint256::from(u128::MAX).try_into().unwrap();
+        assert_eq!(val, Uint128::from(u128::MAX));
     }
 
     #[test]
-    #[should_panic]
-    fn u256_into_u128_panics_for_overflow() {
-        let _: u128 = Uint256::from_str("2134982317498312749832174923184732198471983247")
-            .unwrap()
-            .into();
+    fn u256_into_u128_fails_for_overflow() {
+        let big = Uint256::from_str("2134982317498312749832174923184732198471983247").unwrap();
+        assert_eq!(
+            u128::try_from(big),
+            Err(ConversionOverflowError::new(
+                "Uint256",
+                "u128",
+                "2134982317498312749832174923184732198471983247"
+            ))
+        );
+        assert_eq!(
+            Uint128::try_from(big),
+            Err(ConversionOverflowError::new(
+                "Uint256",
+                "Uint128",
+                "2134982317498312749832174923184732198471983247"
+            ))
+        );
+
+        // the error integrates with StdError
+        let err: StdError = u128::try_from(big).unwrap_err().into();
+        match err {
+            StdError::ConversionOverflow { source, .. } => assert_eq!(source.target_type, "u128"),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn decimal256_into_decimal() {
+        assert_eq!(
+            Decimal::try_from(Decimal256::percent(150)).unwrap(),
+            Decimal::percent(150)
+        );
+        let max = Decimal256::new(Uint256::from(u128::MAX).0);
+        assert_eq!(Decimal::try_from(max).unwrap().to_string(), max.to_string());
+        assert_eq!(
+            Decimal::try_from(Decimal256::MAX),
+            Err(ConversionOverflowError::new(
+                "Decimal256",
+                "Decimal",
+                Decimal256::MAX.to_string()
+            ))
+        );
     }
 
     #[test]
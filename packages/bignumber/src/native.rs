@@ -0,0 +1,110 @@
+//! Conversions to and from the Uint256 and Decimal256 types that ship with cosmwasm-std 1.x,
+//! enabled by the `std-interop` feature on non-wasm targets, e.g. for tests or off-chain
+//! tooling that handles values of both libraries.
+//!
+//! Both libraries store the same 256-bit value and use 18 fractional digits, so the
+//! conversions in either direction are lossless. The native types are re-exported here
+//! together with `NativeDecimal256Ext`, which adds `from_uint256`. This is not a drop-in
+//! replacement: the rest of this crate's API, such as `FixedDecimal`, the signed types and
+//! the rounding helpers, has no counterpart in cosmwasm-std 1.x.
+
+use bigint::U256;
+use cosmwasm_std_v1 as std_v1;
+
+use crate::math;
+
+pub use std_v1::{Decimal256, Uint256};
+
+/// The part of the cosmwasm-bignumber API that the native Decimal256 does not provide.
+/// `percent`, `permille` and `Uint256::multiply_ratio` exist natively.
+pub trait NativeDecimal256Ext {
+    fn from_uint256<A: Into<Uint256>>(val: A) -> Self;
+}
+
+impl NativeDecimal256Ext for Decimal256 {
+    fn from_uint256<A: Into<Uint256>>(val: A) -> Self {
+        Decimal256::from_ratio(val, 1u8)
+    }
+}
+
+impl From<math::Uint256> for Uint256 {
+    fn from(val: math::Uint256) -> Self {
+        let mut bytes = [0u8; 32];
+        val.0.to_big_endian(&mut bytes);
+        Uint256::from_be_bytes(bytes)
+    }
+}
+
+impl From<Uint256> for math::Uint256 {
+    fn from(val: Uint256) -> Self {
+        math::Uint256(U256::from_big_endian(&val.to_be_bytes()))
+    }
+}
+
+impl From<math::Decimal256> for Decimal256 {
+    fn from(val: math::Decimal256) -> Self {
+        Decimal256::new(math::Uint256(val.0).into())
+    }
+}
+
+impl From<Decimal256> for math::Decimal256 {
+    fn from(val: Decimal256) -> Self {
+        math::Decimal256::new(math::Uint256::from(val.atomics()).0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn uint256_round_trip() {
+        for s in [
+            "0",
+            "1",
+            "18446744073709551616",
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+        ] {
+            let ours = math::Uint256::from_str(s).unwrap();
+            let native = Uint256::from(ours);
+            assert_eq!(native.to_string(), s);
+            assert_eq!(math::Uint256::from(native), ours);
+        }
+    }
+
+    #[test]
+    fn decimal256_round_trip() {
+        for s in [
+            "0",
+            "0.000000000000000001",
+            "1.5",
+            "115792089237316195423570985008687907853269984665640564039457.584007913129639935",
+        ] {
+            let ours = math::Decimal256::from_str(s).unwrap();
+            let native = Decimal256::from(ours);
+            assert_eq!(native.to_string(), s);
+            assert_eq!(math::Decimal256::from(native), ours);
+        }
+    }
+
+    #[test]
+    fn native_types_keep_the_bignumber_api() {
+        assert_eq!(
+            Decimal256::percent(50),
+            Decimal256::from(math::Decimal256::percent(50))
+        );
+        assert_eq!(
+            Decimal256::permille(5),
+            Decimal256::from(math::Decimal256::permille(5))
+        );
+        assert_eq!(
+            Decimal256::from_uint256(42u128),
+            Decimal256::from(math::Decimal256::from_uint256(42u64))
+        );
+        assert_eq!(
+            Uint256::from(500u128).multiply_ratio(2u128, 3u128),
+            Uint256::from(math::Uint256::from(500u64).multiply_ratio(2u64, 3u64))
+        );
+    }
+}
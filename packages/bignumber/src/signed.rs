@@ -0,0 +1,556 @@
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::{de, ser, Deserialize, Deserializer, Serialize};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops;
+use std::str::FromStr;
+
+use cosmwasm_std::{
+    ConversionOverflowError, DivideByZeroError, OverflowError, OverflowOperation, StdError,
+};
+
+use crate::math::{CheckedFromRatioError, Decimal256, Uint256};
+
+/// Implements the sign-magnitude arithmetic, ordering, parsing and string serde shared by
+/// the signed types. `$abs` must provide zero(), is_zero(), checked_add/sub/mul and the
+/// corresponding panicking operators.
+macro_rules! impl_signed {
+    ($t:ident, $abs:ident, $visitor:ident, $expecting:expr) => {
+        impl $t {
+            pub const fn zero() -> Self {
+                $t {
+                    abs: $abs::zero(),
+                    negative: false,
+                }
+            }
+
+            /// Create a value from its absolute value and sign. Zero is never negative.
+            pub fn new(abs: $abs, negative: bool) -> Self {
+                $t {
+                    abs,
+                    negative: negative && !abs.is_zero(),
+                }
+            }
+
+            pub fn abs(&self) -> $abs {
+                self.abs
+            }
+
+            pub fn is_negative(&self) -> bool {
+                self.negative
+            }
+
+            pub fn is_zero(&self) -> bool {
+                self.abs.is_zero()
+            }
+
+            pub fn checked_add(self, other: Self) -> Result<Self, OverflowError> {
+                let overflow = || OverflowError::new(OverflowOperation::Add, self, other);
+                if self.negative == other.negative {
+                    let abs = self.abs.checked_add(other.abs).map_err(|_| overflow())?;
+                    return Ok($t::new(abs, self.negative));
+                }
+
+                // opposite signs never overflow, the result takes the sign of the larger value
+                if self.abs >= other.abs {
+                    Ok($t::new(self.abs - other.abs, self.negative))
+                } else {
+                    Ok($t::new(other.abs - self.abs, other.negative))
+                }
+            }
+
+            pub fn checked_sub(self, other: Self) -> Result<Self, OverflowError> {
+                self.checked_add(-other)
+                    .map_err(|_| OverflowError::new(OverflowOperation::Sub, self, other))
+            }
+
+            pub fn checked_mul(self, other: Self) -> Result<Self, OverflowError> {
+                let abs = self
+                    .abs
+                    .checked_mul(other.abs)
+                    .map_err(|_| OverflowError::new(OverflowOperation::Mul, self, other))?;
+                Ok($t::new(abs, self.negative != other.negative))
+            }
+        }
+
+        impl From<$abs> for $t {
+            fn from(val: $abs) -> Self {
+                $t::new(val, false)
+            }
+        }
+
+        impl TryFrom<$t> for $abs {
+            type Error = ConversionOverflowError;
+
+            fn try_from(val: $t) -> Result<Self, Self::Error> {
+                if val.negative {
+                    return Err(ConversionOverflowError::new(
+                        stringify!($t),
+                        stringify!($abs),
+                        val.to_string(),
+                    ));
+                }
+                Ok(val.abs)
+            }
+        }
+
+        impl FromStr for $t {
+            type Err = StdError;
+
+            fn from_str(input: &str) -> Result<Self, Self::Err> {
+                match input.strip_prefix('-') {
+                    Some(abs) => Ok($t::new($abs::from_str(abs)?, true)),
+                    None => Ok($t::new($abs::from_str(input)?, false)),
+                }
+            }
+        }
+
+        impl fmt::Display for $t {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                if self.negative {
+                    write!(f, "-{}", self.abs)
+                } else {
+                    write!(f, "{}", self.abs)
+                }
+            }
+        }
+
+        impl PartialOrd for $t {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $t {
+            fn cmp(&self, other: &Self) -> Ordering {
+                match (self.negative, other.negative) {
+                    (false, false) => self.abs.cmp(&other.abs),
+                    (true, true) => other.abs.cmp(&self.abs),
+                    (false, true) => Ordering::Greater,
+                    (true, false) => Ordering::Less,
+                }
+            }
+        }
+
+        impl ops::Neg for $t {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                $t::new(self.abs, !self.negative)
+            }
+        }
+
+        impl ops::Add for $t {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                match self.checked_add(rhs) {
+                    Ok(val) => val,
+                    Err(e) => panic!("{}", e),
+                }
+            }
+        }
+
+        impl ops::Sub for $t {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                match self.checked_sub(rhs) {
+                    Ok(val) => val,
+                    Err(e) => panic!("{}", e),
+                }
+            }
+        }
+
+        impl ops::Mul for $t {
+            type Output = Self;
+
+            fn mul(self, rhs: Self) -> Self {
+                match self.checked_mul(rhs) {
+                    Ok(val) => val,
+                    Err(e) => panic!("{}", e),
+                }
+            }
+        }
+
+        impl ops::Div for $t {
+            type Output = Self;
+
+            fn div(self, rhs: Self) -> Self {
+                $t::new(self.abs / rhs.abs, self.negative != rhs.negative)
+            }
+        }
+
+        /// The remainder takes the sign of the dividend, like the primitive integer types
+        impl ops::Rem for $t {
+            type Output = Self;
+
+            fn rem(self, rhs: Self) -> Self {
+                $t::new(self.abs % rhs.abs, self.negative)
+            }
+        }
+
+        impl ops::AddAssign for $t {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl ops::SubAssign for $t {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl ops::MulAssign for $t {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl ops::DivAssign for $t {
+            fn div_assign(&mut self, rhs: Self) {
+                *self = *self / rhs;
+            }
+        }
+
+        impl ops::RemAssign for $t {
+            fn rem_assign(&mut self, rhs: Self) {
+                *self = *self % rhs;
+            }
+        }
+
+        impl JsonSchema for $t {
+            fn schema_name() -> String {
+                stringify!($t).to_string()
+            }
+
+            fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+                String::json_schema(gen)
+            }
+        }
+
+        /// Serializes as a string, with a leading '-' for negative values
+        impl Serialize for $t {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: ser::Serializer,
+            {
+                serializer.serialize_str(&self.to_string())
+            }
+        }
+
+        /// Deserializes from a string, with a leading '-' for negative values
+        impl<'de> Deserialize<'de> for $t {
+            fn deserialize<D>(deserializer: D) -> Result<$t, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                deserializer.deserialize_str($visitor)
+            }
+        }
+
+        struct $visitor;
+
+        impl<'de> de::Visitor<'de> for $visitor {
+            type Value = $t;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str($expecting)
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                match $t::from_str(v) {
+                    Ok(d) => Ok(d),
+                    Err(e) => Err(E::custom(format!(
+                        "invalid {} '{}': {}",
+                        stringify!($t),
+                        v,
+                        e
+                    ))),
+                }
+            }
+        }
+    };
+}
+
+//*** Int256 ***/
+/// A signed integer with a 256-bit magnitude, i.e. ranging from -(2^256 - 1) to 2^256 - 1
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub struct Int256 {
+    abs: Uint256,
+    negative: bool,
+}
+
+impl_signed!(
+    Int256,
+    Uint256,
+    Int256Visitor,
+    "string-encoded signed integer"
+);
+
+impl Int256 {
+    pub fn one() -> Self {
+        Int256::from(Uint256::one())
+    }
+
+    /// Division truncated towards zero
+    pub fn checked_div(self, other: Self) -> Result<Self, DivideByZeroError> {
+        let abs = self.abs.checked_div(other.abs)?;
+        Ok(Int256::new(abs, self.negative != other.negative))
+    }
+}
+
+impl From<i64> for Int256 {
+    fn from(val: i64) -> Self {
+        Int256::new(Uint256::from(val.unsigned_abs()), val < 0)
+    }
+}
+
+impl From<i128> for Int256 {
+    fn from(val: i128) -> Self {
+        Int256::new(Uint256::from(val.unsigned_abs()), val < 0)
+    }
+}
+
+//*** SignedDecimal256 ***/
+/// A signed decimal with 18 fractional digits and a Decimal256 magnitude, i.e. ranging from
+/// -Decimal256::MAX to Decimal256::MAX. Products and quotients are truncated towards zero.
+/// Parses and prints an optional leading '-', e.g. "-1.5".
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub struct SignedDecimal256 {
+    abs: Decimal256,
+    negative: bool,
+}
+
+impl_signed!(
+    SignedDecimal256,
+    Decimal256,
+    SignedDecimal256Visitor,
+    "string-encoded signed decimal"
+);
+
+impl SignedDecimal256 {
+    pub const fn one() -> Self {
+        SignedDecimal256 {
+            abs: Decimal256::one(),
+            negative: false,
+        }
+    }
+
+    pub fn checked_div(self, other: Self) -> Result<Self, CheckedFromRatioError> {
+        let abs = self.abs.checked_div(other.abs)?;
+        Ok(SignedDecimal256::new(abs, self.negative != other.negative))
+    }
+}
+
+/// Int256 * SignedDecimal256 truncated towards zero, like Uint256 * Decimal256
+impl ops::Mul<SignedDecimal256> for Int256 {
+    type Output = Int256;
+
+    fn mul(self, rhs: SignedDecimal256) -> Self::Output {
+        Int256::new(self.abs * rhs.abs, self.negative != rhs.negative)
+    }
+}
+
+impl ops::Mul<Int256> for SignedDecimal256 {
+    type Output = Int256;
+
+    fn mul(self, rhs: Int256) -> Self::Output {
+        rhs * self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cosmwasm_std::{from_slice, to_vec};
+
+    fn int(s: &str) -> Int256 {
+        Int256::from_str(s).unwrap()
+    }
+
+    fn dec(s: &str) -> SignedDecimal256 {
+        SignedDecimal256::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn int256_from_str_and_display() {
+        assert_eq!(int("-123"), Int256::from(-123i64));
+        assert_eq!(int("123"), Int256::from(123i128));
+        assert_eq!(int("-0"), Int256::zero());
+        assert!(!int("-0").is_negative());
+        assert_eq!(int("-123").to_string(), "-123");
+        assert_eq!(int("-123").abs(), Uint256::from(123u64));
+        assert!(Int256::from_str("--1").is_err());
+        assert!(Int256::from_str("1.5").is_err());
+        assert_eq!(
+            Int256::from(i128::MIN).to_string(),
+            "-170141183460469231731687303715884105728"
+        );
+    }
+
+    #[test]
+    fn int256_math() {
+        assert_eq!(int("5") + int("-8"), int("-3"));
+        assert_eq!(int("-5") + int("8"), int("3"));
+        assert_eq!(int("-5") + int("-8"), int("-13"));
+        assert_eq!(int("5") - int("8"), int("-3"));
+        assert_eq!(int("-5") - int("-5"), Int256::zero());
+        assert!(!(int("-5") - int("-5")).is_negative());
+        assert_eq!(int("-5") * int("8"), int("-40"));
+        assert_eq!(int("-5") * int("-8"), int("40"));
+        assert_eq!(int("-7") / int("2"), int("-3"));
+        assert_eq!(int("-7") % int("2"), int("-1"));
+        assert_eq!(int("7") % int("-2"), int("1"));
+        assert_eq!(-int("7"), int("-7"));
+
+        let mut a = int("10");
+        a -= int("25");
+        assert_eq!(a, int("-15"));
+        a *= int("-2");
+        assert_eq!(a, int("30"));
+        a /= int("-4");
+        assert_eq!(a, int("-7"));
+        a += Int256::one();
+        assert_eq!(a, int("-6"));
+        a %= int("4");
+        assert_eq!(a, int("-2"));
+
+        assert_eq!(int("-300") * dec("1.5"), int("-450"));
+        assert_eq!(dec("-0.5") * int("-301"), int("150"));
+    }
+
+    #[test]
+    fn int256_checked_math() {
+        let max = Int256::from(Uint256(bigint::U256::MAX));
+        let min = -max;
+
+        assert_eq!(max.checked_add(int("-1")), Ok(max - Int256::one()));
+        assert_eq!(
+            max.checked_add(Int256::one()),
+            Err(OverflowError::new(
+                OverflowOperation::Add,
+                max,
+                Int256::one()
+            ))
+        );
+        assert_eq!(
+            min.checked_sub(Int256::one()),
+            Err(OverflowError::new(
+                OverflowOperation::Sub,
+                min,
+                Int256::one()
+            ))
+        );
+        assert_eq!(min.checked_add(max), Ok(Int256::zero()));
+        assert!(min.checked_mul(int("2")).is_err());
+        assert_eq!(min.checked_mul(int("-1")), Ok(max));
+        assert_eq!(int("-9").checked_div(int("3")), Ok(int("-3")));
+        assert!(int("-9").checked_div(Int256::zero()).is_err());
+    }
+
+    #[test]
+    fn int256_compare() {
+        let mut values = vec![int("3"), int("-1"), int("0"), int("-20"), int("2")];
+        values.sort();
+        assert_eq!(
+            values,
+            vec![int("-20"), int("-1"), int("0"), int("2"), int("3")]
+        );
+        assert!(int("-1") > int("-2"));
+        assert!(int("-1") < Int256::zero());
+    }
+
+    #[test]
+    fn int256_conversions() {
+        assert_eq!(Int256::from(Uint256::from(5u64)), int("5"));
+        assert_eq!(Uint256::try_from(int("5")).unwrap(), Uint256::from(5u64));
+        assert_eq!(
+            Uint256::try_from(int("-5")),
+            Err(ConversionOverflowError::new("Int256", "Uint256", "-5"))
+        );
+    }
+
+    #[test]
+    fn int256_serde() {
+        assert_eq!(to_vec(&int("-87")).unwrap(), br#""-87""#);
+        assert_eq!(from_slice::<Int256>(br#""-87""#).unwrap(), int("-87"));
+        assert_eq!(from_slice::<Int256>(br#""87""#).unwrap(), int("87"));
+        assert!(from_slice::<Int256>(br#""-8.7""#).is_err());
+    }
+
+    #[test]
+    fn signed_decimal256_math() {
+        assert_eq!(dec("1.5") + dec("-2.25"), dec("-0.75"));
+        assert_eq!(dec("-1.5") - dec("-2.25"), dec("0.75"));
+        assert_eq!(dec("-1.5") * dec("2"), dec("-3"));
+        assert_eq!(dec("-1.5") * dec("-0.5"), dec("0.75"));
+        assert_eq!(dec("-3") / dec("2"), dec("-1.5"));
+        assert_eq!(dec("-3.5") % dec("2"), dec("-1.5"));
+        assert_eq!(-SignedDecimal256::one(), dec("-1"));
+
+        let mut a = dec("0.1");
+        a -= dec("0.3");
+        assert_eq!(a, dec("-0.2"));
+        a *= dec("-5");
+        assert_eq!(a, SignedDecimal256::one());
+        a /= dec("-4");
+        assert_eq!(a, dec("-0.25"));
+        a += dec("0.5");
+        assert_eq!(a, dec("0.25"));
+        a %= dec("0.1");
+        assert_eq!(a, dec("0.05"));
+
+        assert_eq!(dec("-3").checked_div(dec("2")).unwrap(), dec("-1.5"));
+        assert_eq!(
+            dec("-3").checked_div(SignedDecimal256::zero()),
+            Err(CheckedFromRatioError::DivideByZero(DivideByZeroError::new(
+                dec("3")
+            )))
+        );
+        let min = SignedDecimal256::new(Decimal256::MAX, true);
+        assert_eq!(
+            min.checked_add(dec("-1")),
+            Err(OverflowError::new(OverflowOperation::Add, min, dec("-1")))
+        );
+        assert!(dec("-2").checked_mul(min).is_err());
+    }
+
+    #[test]
+    fn signed_decimal256_conversions_and_compare() {
+        assert_eq!(SignedDecimal256::from(Decimal256::percent(150)), dec("1.5"));
+        assert_eq!(
+            Decimal256::try_from(dec("1.5")).unwrap(),
+            Decimal256::percent(150)
+        );
+        assert!(Decimal256::try_from(dec("-1.5")).is_err());
+        assert_eq!(dec("-0"), SignedDecimal256::zero());
+        assert_eq!(dec("-1.5").abs(), Decimal256::percent(150));
+
+        assert!(dec("-1.5") < dec("-1.25"));
+        assert!(dec("-1.5") < dec("0.1"));
+        assert!(dec("1.5") > dec("1.25"));
+    }
+
+    #[test]
+    fn signed_decimal256_serde() {
+        let value = SignedDecimal256::new(Decimal256::percent(8765), true);
+        assert_eq!(to_vec(&value).unwrap(), br#""-87.65""#);
+        assert_eq!(
+            from_slice::<SignedDecimal256>(br#""-87.65""#).unwrap(),
+            value
+        );
+        assert_eq!(
+            from_slice::<SignedDecimal256>(br#""0.08""#).unwrap(),
+            SignedDecimal256::from(Decimal256::percent(8))
+        );
+        assert!(from_slice::<SignedDecimal256>(br#""--1""#).is_err());
+    }
+}
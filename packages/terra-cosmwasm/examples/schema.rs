@@ -3,8 +3,13 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 use terra_cosmwasm::{
-    ExchangeRatesResponse, SwapResponse, TaxCapResponse, TaxRateResponse, TerraMsg,
-    TerraMsgWrapper, TerraQuery, TerraQueryWrapper, TerraRoute,
+    ActivesResponse, CodeInfoResponse, ContractInfoResponse, ExchangeRateResponse,
+    ExchangeRatesResponse, FeederDelegationResponse, IndicatorsResponse, MarketParametersResponse,
+    MissCounterResponse, OracleParametersResponse, RawStoreResponse, RewardWeightResponse,
+    SeigniorageProceedsResponse, SwapResponse, TaxCapResponse, TaxProceedsResponse,
+    TaxRateResponse, TerraMsg, TerraMsgWrapper, TerraPoolDeltaResponse, TerraQuery,
+    TerraQueryWrapper, TerraRoute, TobinTaxResponse, TobinTaxesResponse,
+    TreasuryParametersResponse, VoteTargetsResponse, WasmParametersResponse,
 };
 
 fn main() {
@@ -19,7 +24,26 @@ fn main() {
     export_schema(&schema_for!(TerraQuery), &out_dir);
     export_schema(&schema_for!(TerraRoute), &out_dir);
     export_schema(&schema_for!(SwapResponse), &out_dir);
+    export_schema(&schema_for!(TerraPoolDeltaResponse), &out_dir);
+    export_schema(&schema_for!(MarketParametersResponse), &out_dir);
     export_schema(&schema_for!(TaxCapResponse), &out_dir);
     export_schema(&schema_for!(TaxRateResponse), &out_dir);
+    export_schema(&schema_for!(RewardWeightResponse), &out_dir);
+    export_schema(&schema_for!(SeigniorageProceedsResponse), &out_dir);
+    export_schema(&schema_for!(TaxProceedsResponse), &out_dir);
+    export_schema(&schema_for!(TreasuryParametersResponse), &out_dir);
+    export_schema(&schema_for!(IndicatorsResponse), &out_dir);
     export_schema(&schema_for!(ExchangeRatesResponse), &out_dir);
+    export_schema(&schema_for!(ExchangeRateResponse), &out_dir);
+    export_schema(&schema_for!(ActivesResponse), &out_dir);
+    export_schema(&schema_for!(VoteTargetsResponse), &out_dir);
+    export_schema(&schema_for!(TobinTaxResponse), &out_dir);
+    export_schema(&schema_for!(TobinTaxesResponse), &out_dir);
+    export_schema(&schema_for!(OracleParametersResponse), &out_dir);
+    export_schema(&schema_for!(MissCounterResponse), &out_dir);
+    export_schema(&schema_for!(FeederDelegationResponse), &out_dir);
+    export_schema(&schema_for!(ContractInfoResponse), &out_dir);
+    export_schema(&schema_for!(CodeInfoResponse), &out_dir);
+    export_schema(&schema_for!(RawStoreResponse), &out_dir);
+    export_schema(&schema_for!(WasmParametersResponse), &out_dir);
 }
@@ -0,0 +1,297 @@
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::{de, ser, Deserialize, Deserializer, Serialize};
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use cosmwasm_std::{StdError, StdResult};
+
+/// Prefix of the denoms of tokens received over IBC, followed by the hash of their trace
+const IBC_DENOM_PREFIX: &str = "ibc/";
+
+/// NativeDenom is a coin issued by Terra Core
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NativeDenom {
+    pub denom: &'static str,
+    /// ISO 4217 code of the currency a stablecoin tracks, None for Luna
+    pub iso_code: Option<&'static str>,
+}
+
+const fn native(denom: &'static str, iso_code: &'static str) -> NativeDenom {
+    NativeDenom {
+        denom,
+        iso_code: Some(iso_code),
+    }
+}
+
+/// NATIVE_DENOMS lists Luna and the Terra stablecoins, which track the currency with their
+/// ISO code. The market module prices swaps through usdr, the IMF's Special Drawing Rights.
+pub const NATIVE_DENOMS: &[NativeDenom] = &[
+    NativeDenom {
+        denom: "uluna",
+        iso_code: None,
+    },
+    native("uaud", "AUD"),
+    native("ucad", "CAD"),
+    native("uchf", "CHF"),
+    native("ucny", "CNY"),
+    native("udkk", "DKK"),
+    native("ueur", "EUR"),
+    native("ugbp", "GBP"),
+    native("uhkd", "HKD"),
+    native("uidr", "IDR"),
+    native("uinr", "INR"),
+    native("ujpy", "JPY"),
+    native("ukrw", "KRW"),
+    native("umnt", "MNT"),
+    native("umyr", "MYR"),
+    native("unok", "NOK"),
+    native("uphp", "PHP"),
+    native("usdr", "XDR"),
+    native("usek", "SEK"),
+    native("usgd", "SGD"),
+    native("uthb", "THB"),
+    native("utwd", "TWD"),
+    native("uusd", "USD"),
+];
+
+/// Denom is a coin denomination that follows the Cosmos SDK denom syntax,
+/// i.e. `[a-zA-Z][a-zA-Z0-9/:._-]{2,127}`, and is serialized as a plain string.
+/// IBC denoms must be `ibc/` followed by a 64 digit hex hash.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Denom(String);
+
+impl Denom {
+    pub fn new<T: Into<String>>(denom: T) -> StdResult<Denom> {
+        let denom = denom.into();
+        validate_denom(&denom)?;
+        Ok(Denom(denom))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the trace hash of an IBC denom
+    pub fn ibc_hash(&self) -> Option<&str> {
+        self.0.strip_prefix(IBC_DENOM_PREFIX)
+    }
+
+    pub fn is_ibc(&self) -> bool {
+        self.ibc_hash().is_some()
+    }
+
+    /// Returns the registry entry of a Terra native denom
+    pub fn native(&self) -> Option<&'static NativeDenom> {
+        NATIVE_DENOMS.iter().find(|native| native.denom == self.0)
+    }
+
+    pub fn is_native(&self) -> bool {
+        self.native().is_some()
+    }
+
+    /// Returns the Terra stablecoin that tracks the currency with the given ISO 4217 code
+    pub fn from_iso_code(iso_code: &str) -> Option<Denom> {
+        NATIVE_DENOMS
+            .iter()
+            .find(|native| native.iso_code == Some(iso_code))
+            .map(|native| Denom(native.denom.to_string()))
+    }
+}
+
+fn validate_denom(denom: &str) -> StdResult<()> {
+    let invalid = |reason: &str| {
+        Err(StdError::generic_err(format!(
+            "Invalid denom '{}': {}",
+            denom, reason
+        )))
+    };
+
+    if denom.len() < 3 || denom.len() > 128 {
+        return invalid("must be between 3 and 128 characters long");
+    }
+    if !denom.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return invalid("must start with a letter");
+    }
+    if !denom
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "/:._-".contains(c))
+    {
+        return invalid("may only contain letters, digits and /:._-");
+    }
+    if let Some(hash) = denom.strip_prefix(IBC_DENOM_PREFIX) {
+        if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return invalid("IBC denoms must be ibc/ followed by a 64 digit hex hash");
+        }
+    }
+
+    Ok(())
+}
+
+impl FromStr for Denom {
+    type Err = StdError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Denom::new(input)
+    }
+}
+
+impl TryFrom<String> for Denom {
+    type Error = StdError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Denom::new(value)
+    }
+}
+
+impl From<Denom> for String {
+    fn from(denom: Denom) -> Self {
+        denom.0
+    }
+}
+
+impl AsRef<str> for Denom {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for Denom {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Denom {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl fmt::Display for Denom {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl JsonSchema for Denom {
+    fn schema_name() -> String {
+        "Denom".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
+/// Serializes as a plain string
+impl Serialize for Denom {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+/// Deserializes from a plain string, failing on invalid denoms
+impl<'de> Deserialize<'de> for Denom {
+    fn deserialize<D>(deserializer: D) -> Result<Denom, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(DenomVisitor)
+    }
+}
+
+struct DenomVisitor;
+
+impl<'de> de::Visitor<'de> for DenomVisitor {
+    type Value = Denom;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("denom string")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Denom::new(v).map_err(|e| E::custom(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cosmwasm_std::{from_slice, to_vec};
+
+    #[test]
+    fn denom_validation() {
+        for denom in [
+            "uluna",
+            "uusd",
+            "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2",
+            "factory/terra1abc/token.v2",
+            "gamm:pool_1",
+        ] {
+            assert_eq!(Denom::new(denom).unwrap().as_str(), denom);
+        }
+
+        for (denom, reason) in [
+            ("u", "must be between 3 and 128 characters long"),
+            ("1uusd", "must start with a letter"),
+            ("uu sd", "may only contain letters, digits and /:._-"),
+            (
+                "ibc/1234",
+                "IBC denoms must be ibc/ followed by a 64 digit hex hash",
+            ),
+        ] {
+            match Denom::new(denom).unwrap_err() {
+                StdError::GenericErr { msg, .. } => {
+                    assert_eq!(msg, format!("Invalid denom '{}': {}", denom, reason))
+                }
+                e => panic!("Unexpected error: {:?}", e),
+            }
+        }
+        assert!(Denom::new("a".repeat(128)).is_ok());
+        assert!(Denom::new("a".repeat(129)).is_err());
+    }
+
+    #[test]
+    fn denom_registry() {
+        let uusd = Denom::new("uusd").unwrap();
+        assert_eq!(uusd.native().unwrap().iso_code, Some("USD"));
+        assert!(!uusd.is_ibc());
+        assert_eq!(Denom::from_iso_code("KRW").unwrap(), "ukrw");
+        assert_eq!(Denom::from_iso_code("XDR").unwrap(), "usdr");
+        assert_eq!(Denom::from_iso_code("XYZ"), None);
+        assert_eq!(
+            Denom::new("uluna").unwrap().native().unwrap().iso_code,
+            None
+        );
+
+        let ibc =
+            Denom::new("ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2")
+                .unwrap();
+        assert!(!ibc.is_native());
+        assert_eq!(
+            ibc.ibc_hash(),
+            Some("27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2")
+        );
+    }
+
+    #[test]
+    fn denom_serde() {
+        let denom = Denom::new("ukrw").unwrap();
+        assert_eq!(to_vec(&denom).unwrap(), br#""ukrw""#);
+        assert_eq!(from_slice::<Denom>(br#""ukrw""#).unwrap(), denom);
+
+        let err = from_slice::<Denom>(br#""u-krw!""#).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Invalid denom 'u-krw!': may only contain letters, digits and /:._-"));
+    }
+}
@@ -0,0 +1,191 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+pub type TerraResult<T> = Result<T, TerraError>;
+
+/// TerraError tells apart the failures of Terra queries that contracts may want to handle.
+/// Errors reported by the chain keep their original message.
+#[derive(Error, Debug, PartialEq)]
+pub enum TerraError {
+    /// The oracle does not know the denom, or has no exchange rate for it
+    #[error("{msg}")]
+    UnknownDenom { msg: String },
+
+    /// The market found no effective oracle price for a denom of the swap
+    #[error("{msg}")]
+    NoPrice { msg: String },
+
+    /// The swap would return nothing, because the offer is too small
+    #[error("{msg}")]
+    ZeroSwapCoin { msg: String },
+
+    /// The offer and ask denoms of the swap are the same
+    #[error("{msg}")]
+    RecursiveSwap { msg: String },
+
+    /// The query could not be serialized or its response could not be parsed
+    #[error("{0}")]
+    Serialization(StdError),
+
+    /// Any other failure, including chain errors not listed above, as returned by cosmwasm-std
+    #[error("{0}")]
+    Std(StdError),
+}
+
+impl From<StdError> for TerraError {
+    fn from(err: StdError) -> Self {
+        match err {
+            // errors returned by the chain, matched on the messages registered by its modules
+            StdError::GenericErr { ref msg, .. } => {
+                let msg = msg.clone();
+                if msg.contains("unknown denom") {
+                    TerraError::UnknownDenom { msg }
+                } else if msg.contains("no price registered with oracle") {
+                    TerraError::NoPrice { msg }
+                } else if msg.contains("zero swap coin") {
+                    TerraError::ZeroSwapCoin { msg }
+                } else if msg.contains("recursive swap") {
+                    TerraError::RecursiveSwap { msg }
+                } else {
+                    TerraError::Std(err)
+                }
+            }
+            StdError::ParseErr { .. } | StdError::SerializeErr { .. } => {
+                TerraError::Serialization(err)
+            }
+            err => TerraError::Std(err),
+        }
+    }
+}
+
+impl From<TerraError> for StdError {
+    fn from(err: TerraError) -> Self {
+        match err {
+            TerraError::Serialization(err) | TerraError::Std(err) => err,
+            err => StdError::generic_err(err.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::querier::TerraQuerier;
+    use crate::query::MarketParametersResponse;
+    use crate::testing::mock_dependencies_with_terra;
+    use cosmwasm_std::{coin, Decimal, QuerierWrapper};
+
+    #[test]
+    fn querier_errors_are_typed() {
+        let mut deps = mock_dependencies_with_terra(&[]);
+        deps.querier.update_exchange_rates(
+            "uluna",
+            &[
+                ("usdr", Decimal::percent(4050)),
+                ("uusd", Decimal::percent(5000)),
+            ],
+        );
+        deps.querier
+            .update_tobin_taxes(&[("uusd", Decimal::permille(3))]);
+        deps.querier
+            .update_market_parameters(MarketParametersResponse {
+                base_pool: Decimal::from_ratio(250_000_000_000_000u128, 1u128),
+                pool_recovery_period: 36,
+                min_stability_spread: Decimal::permille(20),
+            });
+        let querier = QuerierWrapper::new(&deps.querier);
+        let terra_querier = TerraQuerier::new(&querier);
+
+        let err = terra_querier
+            .query_exchange_rate("uluna", "ukrw")
+            .unwrap_err();
+        match &err {
+            TerraError::UnknownDenom { msg } => assert!(msg.ends_with("ukrw: unknown denom")),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+        // converting back yields the error query_exchange_rate used to return
+        match StdError::from(err) {
+            StdError::GenericErr { msg, .. } => {
+                assert_eq!(msg, "Querier contract error: ukrw: unknown denom")
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+
+        match terra_querier.query_swap(coin(1, "uusd"), "uluna") {
+            Err(TerraError::ZeroSwapCoin { .. }) => {}
+            res => panic!("Unexpected result: {:?}", res),
+        }
+        match terra_querier.query_swap(coin(1_000_000, "uusd"), "uusd") {
+            Err(TerraError::RecursiveSwap { .. }) => {}
+            res => panic!("Unexpected result: {:?}", res),
+        }
+
+        // misspelled denoms are rejected before the query reaches the chain
+        match terra_querier.query_tax_cap("uu sd") {
+            Err(TerraError::Std(StdError::GenericErr { msg, .. })) => assert_eq!(
+                msg,
+                "Invalid denom 'uu sd': may only contain letters, digits and /:._-"
+            ),
+            res => panic!("Unexpected result: {:?}", res),
+        }
+        assert!(terra_querier
+            .query_swap(coin(1_000_000, "uusd!"), "uluna")
+            .is_err());
+        assert!(terra_querier
+            .query_exchange_rates("uluna", vec!["uusd", "1ukrw"])
+            .is_err());
+    }
+
+    #[test]
+    fn chain_error_strings_are_classified() {
+        // messages of the errors registered by Terra Core's market and oracle modules, as they
+        // reach a contract; a change of these strings in the chain breaks the classification
+        let classified = |msg: &str| TerraError::from(StdError::generic_err(msg));
+        let msg = "Querier contract error: ukrw: unknown denom";
+        assert_eq!(
+            classified(msg),
+            TerraError::UnknownDenom {
+                msg: msg.to_string()
+            }
+        );
+        let msg = "Querier contract error: usdr: no price registered with oracle";
+        assert_eq!(
+            classified(msg),
+            TerraError::NoPrice {
+                msg: msg.to_string()
+            }
+        );
+        let msg = "Querier contract error: uluna: zero swap coin";
+        assert_eq!(
+            classified(msg),
+            TerraError::ZeroSwapCoin {
+                msg: msg.to_string()
+            }
+        );
+        let msg = "Querier contract error: uusd: recursive swap";
+        assert_eq!(
+            classified(msg),
+            TerraError::RecursiveSwap {
+                msg: msg.to_string()
+            }
+        );
+        let msg = "Querier contract error: insufficient funds";
+        assert_eq!(classified(msg), TerraError::Std(StdError::generic_err(msg)));
+    }
+
+    #[test]
+    fn std_errors_are_classified() {
+        assert_eq!(
+            TerraError::from(StdError::parse_err("TaxRateResponse", "missing field")),
+            TerraError::Serialization(StdError::parse_err("TaxRateResponse", "missing field"))
+        );
+        assert_eq!(
+            TerraError::from(StdError::not_found("contract")),
+            TerraError::Std(StdError::not_found("contract"))
+        );
+        assert_eq!(
+            StdError::from(TerraError::Std(StdError::not_found("contract"))),
+            StdError::not_found("contract")
+        );
+    }
+}
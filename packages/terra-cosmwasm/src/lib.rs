@@ -1,15 +1,40 @@
+mod denom;
+mod error;
+pub mod market;
 mod msg;
 mod querier;
 mod query;
+mod reply;
 mod route;
+mod tax;
+pub mod terra_address;
 
-pub use msg::{create_swap_msg, create_swap_send_msg, TerraMsg, TerraMsgWrapper};
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
+pub use denom::{Denom, NativeDenom, NATIVE_DENOMS};
+pub use error::{TerraError, TerraResult};
+pub use msg::{
+    create_send_msg_deduct_tax, create_send_msg_gross_up, create_swap_msg,
+    create_swap_msg_with_limit, create_swap_send_msg, create_swap_send_msg_with_limit, SwapLimit,
+    TerraMsg, TerraMsgWrapper,
+};
 pub use querier::TerraQuerier;
 pub use query::{
-    ContractInfoResponse, ExchangeRateItem, ExchangeRatesResponse, SwapResponse, TaxCapResponse,
-    TaxRateResponse, TerraQuery, TerraQueryWrapper,
+    ActivesResponse, CodeInfoResponse, ContractInfoResponse, ExchangeRateItem,
+    ExchangeRateResponse, ExchangeRatesResponse, FeederDelegationResponse, IndicatorsResponse,
+    MarketParametersResponse, MissCounterResponse, OracleParametersResponse, PolicyConstraints,
+    RawStoreResponse, RewardWeightResponse, SeigniorageProceedsResponse, SwapResponse,
+    TaxCapResponse, TaxProceedsResponse, TaxRateResponse, TerraPoolDeltaResponse, TerraQuery,
+    TerraQueryWrapper, TobinTaxItem, TobinTaxResponse, TobinTaxesResponse,
+    TreasuryParametersResponse, VoteTargetsResponse, WasmParametersResponse,
 };
+pub use reply::{parse_swap_reply, DecCoin, SwapResult};
 pub use route::TerraRoute;
+pub use tax::{add_tax, compute_tax, deduct_tax};
+
+// the sign-magnitude decimal of TerraPoolDeltaResponse
+pub use cosmwasm_bignumber::SignedDecimal256;
 
 // This export is added to all contracts that import this package, signifying that they require
 // "terra" support on the chain they run on.
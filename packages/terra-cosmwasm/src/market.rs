@@ -0,0 +1,577 @@
+use bigint::{U256, U512};
+use cosmwasm_std::{Coin, CosmosMsg, Decimal, Fraction, StdError, StdResult, Uint128};
+
+use crate::denom::Denom;
+use crate::msg::{create_swap_msg, TerraMsgWrapper};
+use crate::querier::TerraQuerier;
+use crate::query::{ExchangeRateItem, MarketParametersResponse, SwapResponse, TobinTaxItem};
+use cosmwasm_bignumber::SignedDecimal256;
+
+const MICRO_LUNA_DENOM: &str = "uluna";
+const MICRO_SDR_DENOM: &str = "usdr";
+
+/// MarketSnapshot holds the chain state that the market module reads to compute a swap
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarketSnapshot {
+    /// Oracle exchange rates with uluna as base denom, i.e. the price of one uluna in each denom.
+    /// Must contain usdr, which is the base denom of the virtual pools.
+    pub luna_exchange_rates: Vec<ExchangeRateItem>,
+    pub tobin_taxes: Vec<TobinTaxItem>,
+    pub market_parameters: MarketParametersResponse,
+    pub terra_pool_delta: SignedDecimal256,
+}
+
+/// SwapSimulation is the result of simulate_swap
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwapSimulation {
+    /// Same as the chain's response to TerraQuery::Swap for the offer
+    pub swap: SwapResponse,
+    /// Spread fee burned by the market module, truncated to an integer amount
+    pub spread_fee: Coin,
+}
+
+/// simulate_swap reproduces Terra core's market swap computation offline. Luna swaps trade
+/// against the constant-product virtual pools, Terra<>Terra swaps only pay the highest Tobin tax
+/// of both denoms. Any spread below the min stability spread is raised to it.
+pub fn simulate_swap(
+    market: &MarketSnapshot,
+    offer_coin: &Coin,
+    ask_denom: &str,
+) -> StdResult<SwapSimulation> {
+    if offer_coin.denom == ask_denom {
+        return Err(StdError::generic_err(format!(
+            "{}: recursive swap",
+            ask_denom
+        )));
+    }
+
+    // swap offer coin to base denom for simplicity of swap process
+    let offer_amount = dec_from_uint(offer_coin.amount);
+    let base_offer_amount =
+        compute_internal_swap(market, offer_amount, &offer_coin.denom, MICRO_SDR_DENOM)?;
+    // get swap amount based on the oracle price
+    let ask_amount = compute_internal_swap(market, base_offer_amount, MICRO_SDR_DENOM, ask_denom)?;
+
+    let spread = if offer_coin.denom != MICRO_LUNA_DENOM && ask_denom != MICRO_LUNA_DENOM {
+        // Terra<>Terra swaps apply only the highest tobin tax of both denoms
+        std::cmp::max(
+            tobin_tax(market, &offer_coin.denom)?,
+            tobin_tax(market, ask_denom)?,
+        )
+    } else {
+        let params = &market.market_parameters;
+        let base_pool = dec_from_decimal(params.base_pool);
+        let min_spread = dec_from_decimal(params.min_stability_spread);
+
+        // constant-product, which by construction is square of base(equilibrium) pool
+        let cp = dec_mul(base_pool, base_pool)?;
+        let delta = market.terra_pool_delta.abs().0;
+        let terra_pool = if market.terra_pool_delta.is_negative() {
+            base_pool.saturating_sub(delta)
+        } else {
+            base_pool + delta
+        };
+        if terra_pool.is_zero() {
+            return Err(StdError::generic_err("terra pool is depleted"));
+        }
+        let luna_pool = dec_quo(cp, terra_pool)?;
+
+        let (offer_pool, ask_pool) = if offer_coin.denom != MICRO_LUNA_DENOM {
+            (terra_pool, luna_pool)
+        } else {
+            (luna_pool, terra_pool)
+        };
+
+        // ask_base_amount = ask_pool - cp / (offer_pool + base_offer_amount)
+        let ask_base_amount =
+            ask_pool.saturating_sub(dec_quo(cp, dec_add(offer_pool, base_offer_amount)?)?);
+
+        // spread = (base_offer_amount - ask_base_amount) / base_offer_amount
+        let spread = dec_quo(
+            base_offer_amount.saturating_sub(ask_base_amount),
+            base_offer_amount,
+        )?;
+        std::cmp::max(spread, min_spread)
+    };
+
+    let fee_amount = dec_mul(spread, ask_amount)?;
+    let receive_amount = ask_amount.saturating_sub(fee_amount);
+    let receive = truncate(receive_amount)?;
+    if receive.is_zero() {
+        return Err(StdError::generic_err(format!(
+            "{}: zero swap coin",
+            ask_denom
+        )));
+    }
+
+    Ok(SwapSimulation {
+        swap: SwapResponse {
+            receive: Coin {
+                denom: ask_denom.to_string(),
+                amount: receive,
+            },
+        },
+        spread_fee: Coin {
+            denom: ask_denom.to_string(),
+            amount: truncate(fee_amount)?,
+        },
+    })
+}
+
+/// SwapRoute is a sequence of market swaps, each one offering what the previous one returned
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwapRoute {
+    /// Offer coin and ask denom of each swap
+    pub swaps: Vec<(Coin, Denom)>,
+    /// Expected return of the last swap
+    pub receive: Coin,
+}
+
+impl SwapRoute {
+    pub fn into_msgs(self) -> Vec<CosmosMsg<TerraMsgWrapper>> {
+        self.swaps
+            .into_iter()
+            .map(|(offer_coin, ask_denom)| create_swap_msg(offer_coin, ask_denom))
+            .collect()
+    }
+}
+
+/// plan_swap_route returns the swap messages that return the most of ask_denom for offer_coin,
+/// either swapping directly or through one of the given intermediate denoms.
+pub fn plan_swap_route(
+    querier: &TerraQuerier,
+    offer_coin: Coin,
+    ask_denom: &str,
+    intermediate_denoms: &[&str],
+) -> StdResult<Vec<CosmosMsg<TerraMsgWrapper>>> {
+    let route = find_best_swap_route(
+        &offer_coin,
+        ask_denom,
+        intermediate_denoms,
+        |offer_coin, ask_denom| Ok(querier.query_swap(offer_coin.clone(), ask_denom)?.receive),
+    )?;
+
+    Ok(route.into_msgs())
+}
+
+/// find_best_swap_route evaluates the direct swap and every one-hop route through the
+/// intermediate denoms with `swap`, which returns what offering a coin for a denom yields,
+/// e.g. backed by TerraQuerier::query_swap or simulate_swap. Every leg is evaluated against
+/// the same market state, so the pool shift caused by the first leg is not accounted for.
+/// Routes that fail to simulate are skipped, the direct swap wins ties.
+pub fn find_best_swap_route<F>(
+    offer_coin: &Coin,
+    ask_denom: &str,
+    intermediate_denoms: &[&str],
+    swap: F,
+) -> StdResult<SwapRoute>
+where
+    F: Fn(&Coin, &str) -> StdResult<Coin>,
+{
+    let ask = Denom::new(ask_denom)?;
+    let intermediates = intermediate_denoms
+        .iter()
+        .filter(|denom| **denom != offer_coin.denom && **denom != ask_denom)
+        .map(|denom| Denom::new(*denom))
+        .collect::<StdResult<Vec<Denom>>>()?;
+
+    let direct = swap(offer_coin, ask_denom).map(|receive| SwapRoute {
+        swaps: vec![(offer_coin.clone(), ask.clone())],
+        receive,
+    });
+
+    intermediates
+        .into_iter()
+        .filter_map(|denom| {
+            let intermediate = swap(offer_coin, denom.as_str()).ok()?;
+            let receive = swap(&intermediate, ask_denom).ok()?;
+            Some(SwapRoute {
+                swaps: vec![(offer_coin.clone(), denom), (intermediate, ask.clone())],
+                receive,
+            })
+        })
+        .fold(direct, |best, route| match best {
+            Ok(best) if best.receive.amount >= route.receive.amount => Ok(best),
+            _ => Ok(route),
+        })
+}
+
+// returns offer_amount * ask_rate / offer_rate, both rates priced against luna
+fn compute_internal_swap(
+    market: &MarketSnapshot,
+    offer_amount: U256,
+    offer_denom: &str,
+    ask_denom: &str,
+) -> StdResult<U256> {
+    if offer_denom == ask_denom {
+        return Ok(offer_amount);
+    }
+
+    let offer_rate = luna_exchange_rate(market, offer_denom)?;
+    let ask_rate = luna_exchange_rate(market, ask_denom)?;
+    let ret_amount = dec_quo(dec_mul(offer_amount, ask_rate)?, offer_rate)?;
+    if ret_amount.is_zero() {
+        return Err(StdError::generic_err(format!(
+            "{}: zero swap coin",
+            ask_denom
+        )));
+    }
+
+    Ok(ret_amount)
+}
+
+fn luna_exchange_rate(market: &MarketSnapshot, denom: &str) -> StdResult<U256> {
+    if denom == MICRO_LUNA_DENOM {
+        return Ok(dec_one());
+    }
+
+    market
+        .luna_exchange_rates
+        .iter()
+        .find(|item| item.quote_denom == denom && !item.exchange_rate.is_zero())
+        .map(|item| dec_from_decimal(item.exchange_rate))
+        .ok_or_else(|| StdError::generic_err(format!("{}: no price registered with oracle", denom)))
+}
+
+fn tobin_tax(market: &MarketSnapshot, denom: &str) -> StdResult<U256> {
+    market
+        .tobin_taxes
+        .iter()
+        .find(|item| item.denom == denom)
+        .map(|item| dec_from_decimal(item.tobin_tax))
+        .ok_or_else(|| StdError::generic_err(format!("{}: unknown denom", denom)))
+}
+
+// The helpers below mirror the cosmos-sdk sdk.Dec type: values are U256 scaled by 10^18,
+// and Mul/Quo round half to even at the 18th decimal, so results match the chain exactly.
+
+fn precision() -> U256 {
+    U256::from(1_000_000_000_000_000_000u64)
+}
+
+fn dec_one() -> U256 {
+    precision()
+}
+
+fn dec_from_uint(val: Uint128) -> U256 {
+    u256_from_u128(val.u128()) * precision()
+}
+
+fn dec_from_decimal(val: Decimal) -> U256 {
+    // Decimal shares the 18 decimal places of sdk.Dec
+    debug_assert_eq!(val.denominator(), 1_000_000_000_000_000_000u128);
+    u256_from_u128(val.numerator())
+}
+
+fn u256_from_u128(val: u128) -> U256 {
+    U256([val as u64, (val >> 64) as u64, 0, 0])
+}
+
+fn dec_add(a: U256, b: U256) -> StdResult<U256> {
+    let (sum, overflow) = a.overflowing_add(b);
+    if overflow {
+        return Err(dec_overflow());
+    }
+    Ok(sum)
+}
+
+fn dec_mul(a: U256, b: U256) -> StdResult<U256> {
+    chop_precision_and_round(a.full_mul(b))
+}
+
+// b must not be zero
+fn dec_quo(a: U256, b: U256) -> StdResult<U256> {
+    let precision = U512::from(precision());
+    let quo = U512::from(a) * precision * precision / U512::from(b);
+    chop_precision_and_round(quo)
+}
+
+// divides by 10^18, rounding half to even, and errors when the result does not fit in 256 bits
+fn chop_precision_and_round(val: U512) -> StdResult<U256> {
+    let precision = U512::from(precision());
+    let quo = val / precision;
+    let rem = val % precision;
+    let half = precision / U512::from(2u64);
+
+    let quo = if rem > half || (rem == half && quo.bit(0)) {
+        quo + U512::one()
+    } else {
+        quo
+    };
+    if quo.bits() > 256 {
+        return Err(dec_overflow());
+    }
+    Ok(U256::from(quo))
+}
+
+fn dec_overflow() -> StdError {
+    StdError::generic_err("swap computation overflows 256 bits")
+}
+
+// drops the decimal places of a sdk.Dec
+fn truncate(val: U256) -> StdResult<Uint128> {
+    let int = val / precision();
+    let U256(ref arr) = int;
+    if arr[2] != 0 || arr[3] != 0 {
+        return Err(StdError::generic_err(format!(
+            "swap amount {} exceeds Uint128",
+            int
+        )));
+    }
+
+    Ok(Uint128::from(((arr[1] as u128) << 64) + arr[0] as u128))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::mock_dependencies_with_terra;
+    use cosmwasm_bignumber::Decimal256;
+    use cosmwasm_std::{coin, QuerierWrapper};
+    use std::str::FromStr;
+
+    fn denom(denom: &str) -> Denom {
+        Denom::new(denom).unwrap()
+    }
+
+    fn mock_market() -> MarketSnapshot {
+        MarketSnapshot {
+            luna_exchange_rates: vec![
+                ExchangeRateItem {
+                    quote_denom: "usdr".to_string(),
+                    exchange_rate: Decimal::from_str("40.5").unwrap(),
+                },
+                ExchangeRateItem {
+                    quote_denom: "uusd".to_string(),
+                    exchange_rate: Decimal::from_str("58.4").unwrap(),
+                },
+                ExchangeRateItem {
+                    quote_denom: "ukrw".to_string(),
+                    exchange_rate: Decimal::from_str("66450.5").unwrap(),
+                },
+            ],
+            tobin_taxes: vec![
+                TobinTaxItem {
+                    denom: "usdr".to_string(),
+                    tobin_tax: Decimal::permille(3),
+                },
+                TobinTaxItem {
+                    denom: "uusd".to_string(),
+                    tobin_tax: Decimal::permille(3),
+                },
+                TobinTaxItem {
+                    denom: "ukrw".to_string(),
+                    tobin_tax: Decimal::permille(35),
+                },
+            ],
+            market_parameters: MarketParametersResponse {
+                base_pool: Decimal::from_str("50000000000000").unwrap(),
+                pool_recovery_period: 36,
+                min_stability_spread: Decimal::permille(20),
+            },
+            terra_pool_delta: SignedDecimal256::zero(),
+        }
+    }
+
+    #[test]
+    fn dec_rounding() {
+        let half = precision() / U256::from(2u64);
+
+        // 0.5 rounds to 0, 1.5 and 2.5 round to 2
+        assert_eq!(
+            chop_precision_and_round(U512::from(half)).unwrap(),
+            U256::zero()
+        );
+        assert_eq!(
+            chop_precision_and_round(U512::from(precision() + half)).unwrap(),
+            U256::from(2u64)
+        );
+        assert_eq!(
+            chop_precision_and_round(U512::from(precision() * U256::from(2u64) + half)).unwrap(),
+            U256::from(2u64)
+        );
+        assert_eq!(
+            chop_precision_and_round(U512::from(precision() + half + U256::one())).unwrap(),
+            U256::from(2u64)
+        );
+
+        // 1 / 3 = 0.333333333333333333
+        assert_eq!(
+            dec_quo(dec_one(), dec_from_uint(Uint128::from(3u128))).unwrap(),
+            U256::from(333_333_333_333_333_333u64)
+        );
+        // 2 / 3 = 0.666666666666666667
+        assert_eq!(
+            dec_quo(
+                dec_from_uint(Uint128::from(2u128)),
+                dec_from_uint(Uint128::from(3u128))
+            )
+            .unwrap(),
+            U256::from(666_666_666_666_666_667u64)
+        );
+
+        // results above 256 bits are errors instead of being narrowed
+        let max = U512::from(U256::MAX);
+        assert_eq!(
+            chop_precision_and_round(max * U512::from(precision())).unwrap(),
+            U256::MAX
+        );
+        assert!(chop_precision_and_round((max + U512::one()) * U512::from(precision())).is_err());
+        assert!(dec_mul(U256::MAX, dec_from_uint(Uint128::from(2u128))).is_err());
+        assert!(dec_quo(U256::MAX, U256::one()).is_err());
+        assert!(dec_add(U256::MAX, U256::one()).is_err());
+    }
+
+    #[test]
+    fn terra_to_terra_swap_pays_tobin_tax() {
+        let market = mock_market();
+
+        // 1000000uusd is worth 1137851027.39726...ukrw, the higher tobin tax of ukrw applies
+        let res = simulate_swap(&market, &coin(1_000_000, "uusd"), "ukrw").unwrap();
+        assert_eq!(res.swap.receive, coin(1_098_026_241, "ukrw"));
+        assert_eq!(res.spread_fee, coin(39_824_785, "ukrw"));
+    }
+
+    #[test]
+    fn luna_swap_pays_at_least_min_spread() {
+        let market = mock_market();
+
+        // tiny swaps against an equilibrium pool only pay the min stability spread
+        let res = simulate_swap(&market, &coin(1_000_000, "uluna"), "uusd").unwrap();
+        assert_eq!(res.swap.receive, coin(57_232_000, "uusd"));
+        assert_eq!(res.spread_fee, coin(1_168_000, "uusd"));
+
+        let res = simulate_swap(&market, &coin(58_400_000, "uusd"), "uluna").unwrap();
+        assert_eq!(res.swap.receive, coin(980_000, "uluna"));
+    }
+
+    #[test]
+    fn luna_swap_spread_grows_with_pool_imbalance() {
+        let mut market = mock_market();
+        let offer = coin(1_000_000_000_000, "uluna");
+
+        // 1000000000000uluna is worth 40500000000000usdr against a 50000000000000usdr pool
+        let balanced = simulate_swap(&market, &offer, "uusd").unwrap();
+        assert_eq!(balanced.swap.receive, coin(32_265_193_370_165, "uusd"));
+        assert_eq!(balanced.spread_fee, coin(26_134_806_629_834, "uusd"));
+
+        // terra was sold to the pool, which makes luna more expensive
+        market.terra_pool_delta =
+            SignedDecimal256::new(Decimal256::from_str("10000000000000").unwrap(), false);
+        let expensive = simulate_swap(&market, &offer, "uusd").unwrap();
+        assert_eq!(expensive.swap.receive, coin(42_645_030_425_963, "uusd"));
+
+        market.terra_pool_delta =
+            SignedDecimal256::new(Decimal256::from_str("10000000000000").unwrap(), true);
+        let cheap = simulate_swap(&market, &offer, "uusd").unwrap();
+        assert_eq!(cheap.swap.receive, coin(22_679_611_650_485, "uusd"));
+    }
+
+    #[test]
+    fn best_swap_route() {
+        let mut market = mock_market();
+        let offer = coin(1_000_000, "uusd");
+        let swap = |market: &MarketSnapshot, offer: &Coin, ask: &str| {
+            Ok(simulate_swap(market, offer, ask)?.swap.receive)
+        };
+
+        // the direct swap pays 3.5% tobin tax, going through luna pays 2% spread twice
+        let route = find_best_swap_route(&offer, "ukrw", &["uluna", "usdr", "umnt"], |o, a| {
+            swap(&market, o, a)
+        })
+        .unwrap();
+        assert_eq!(
+            route,
+            SwapRoute {
+                swaps: vec![(offer.clone(), denom("ukrw"))],
+                receive: coin(1_098_026_241, "ukrw"),
+            }
+        );
+
+        // with 5% tobin tax, going through luna is cheaper
+        market.tobin_taxes[2].tobin_tax = Decimal::percent(5);
+        let route = find_best_swap_route(&offer, "ukrw", &["uluna", "usdr", "umnt"], |o, a| {
+            swap(&market, o, a)
+        })
+        .unwrap();
+        assert_eq!(
+            route.swaps,
+            vec![
+                (offer.clone(), denom("uluna")),
+                (coin(16_780, "uluna"), denom("ukrw")),
+            ]
+        );
+        assert_eq!(route.receive, coin(1_092_738_602, "ukrw"));
+        assert_eq!(
+            route.into_msgs(),
+            vec![
+                create_swap_msg(offer.clone(), denom("uluna")),
+                create_swap_msg(coin(16_780, "uluna"), denom("ukrw")),
+            ]
+        );
+
+        // no route at all
+        assert!(
+            find_best_swap_route(&offer, "umnt", &["uluna"], |o, a| swap(&market, o, a)).is_err()
+        );
+    }
+
+    #[test]
+    fn plan_swap_route_with_querier() {
+        let mut deps = mock_dependencies_with_terra(&[]);
+        deps.querier.update_exchange_rates(
+            "uluna",
+            &[
+                ("usdr", Decimal::percent(4050)),
+                ("uusd", Decimal::percent(5840)),
+            ],
+        );
+        deps.querier
+            .update_tobin_taxes(&[("usdr", Decimal::percent(5)), ("uusd", Decimal::percent(5))]);
+        deps.querier
+            .update_market_parameters(mock_market().market_parameters);
+
+        let querier = QuerierWrapper::new(&deps.querier);
+        let terra_querier = TerraQuerier::new(&querier);
+
+        let msgs =
+            plan_swap_route(&terra_querier, coin(1_000_000, "uusd"), "usdr", &["uluna"]).unwrap();
+        assert_eq!(
+            msgs,
+            vec![
+                create_swap_msg(coin(1_000_000, "uusd"), denom("uluna")),
+                create_swap_msg(coin(16_780, "uluna"), denom("usdr")),
+            ]
+        );
+    }
+
+    #[test]
+    fn swap_errors() {
+        let market = mock_market();
+
+        match simulate_swap(&market, &coin(1_000, "uusd"), "uusd").unwrap_err() {
+            StdError::GenericErr { msg, .. } => assert_eq!(msg, "uusd: recursive swap"),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+        match simulate_swap(&market, &coin(1_000, "uusd"), "umnt").unwrap_err() {
+            StdError::GenericErr { msg, .. } => {
+                assert_eq!(msg, "umnt: no price registered with oracle")
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+        match simulate_swap(&market, &coin(1, "uusd"), "uluna").unwrap_err() {
+            StdError::GenericErr { msg, .. } => assert!(msg.ends_with("zero swap coin")),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+
+        // a dust exchange rate makes the internal swap overflow sdk.Dec
+        let mut market = mock_market();
+        market.luna_exchange_rates[1].exchange_rate = Decimal::from_ratio(1u128, 10u128.pow(18));
+        match simulate_swap(&market, &coin(u128::MAX, "uusd"), "ukrw").unwrap_err() {
+            StdError::GenericErr { msg, .. } => {
+                assert_eq!(msg, "swap computation overflows 256 bits")
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+}
@@ -1,17 +1,66 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
 
+use crate::denom::Denom;
+use crate::querier::TerraQuerier;
 use crate::route::TerraRoute;
-use cosmwasm_std::{Coin, CosmosMsg};
+use crate::tax::{add_taxes, deduct_taxes};
+use cosmwasm_bignumber::{Decimal256, Uint256};
+use cosmwasm_std::{BankMsg, Coin, CosmosMsg, Decimal, StdError, StdResult, Uint128};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-#[serde(rename_all = "snake_case")]
-/// TerraMsgWrapper is an override of CosmosMsg::Custom to show this works and can be extended in the contract
+#[serde(rename_all = "snake_case", try_from = "UncheckedTerraMsgWrapper")]
+/// TerraMsgWrapper is an override of CosmosMsg::Custom to show this works and can be extended in the contract.
+/// Deserialization fails when `route` is not the route of `msg_data`.
 pub struct TerraMsgWrapper {
     pub route: TerraRoute,
     pub msg_data: TerraMsg,
 }
 
+// wraps the msg with the route it belongs to
+impl From<TerraMsg> for TerraMsgWrapper {
+    fn from(msg_data: TerraMsg) -> Self {
+        TerraMsgWrapper {
+            route: msg_data.route(),
+            msg_data,
+        }
+    }
+}
+
+/// TerraMsgWrapper as received, before its route is checked against its msg
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+struct UncheckedTerraMsgWrapper {
+    route: TerraRoute,
+    msg_data: TerraMsg,
+}
+
+impl TryFrom<UncheckedTerraMsgWrapper> for TerraMsgWrapper {
+    type Error = String;
+
+    fn try_from(unchecked: UncheckedTerraMsgWrapper) -> Result<Self, Self::Error> {
+        let expected = unchecked.msg_data.route();
+        if unchecked.route != expected {
+            return Err(format!(
+                "Route {:?} does not match msg_data, which belongs to route {:?}",
+                unchecked.route, expected
+            ));
+        }
+        // Coin does not validate its denom
+        match &unchecked.msg_data {
+            TerraMsg::Swap { offer_coin, .. } | TerraMsg::SwapSend { offer_coin, .. } => {
+                Denom::new(offer_coin.denom.as_str()).map_err(|e| e.to_string())?;
+            }
+        }
+
+        Ok(TerraMsgWrapper {
+            route: unchecked.route,
+            msg_data: unchecked.msg_data,
+        })
+    }
+}
+
 // this is a helper to be able to return these as CosmosMsg easier
 impl From<TerraMsgWrapper> for CosmosMsg<TerraMsgWrapper> {
     fn from(original: TerraMsgWrapper) -> Self {
@@ -24,24 +73,30 @@ impl From<TerraMsgWrapper> for CosmosMsg<TerraMsgWrapper> {
 pub enum TerraMsg {
     Swap {
         offer_coin: Coin,
-        ask_denom: String,
+        ask_denom: Denom,
     },
     SwapSend {
         to_address: String,
         offer_coin: Coin,
-        ask_denom: String,
+        ask_denom: Denom,
     },
 }
 
-// create_swap_msg returns wrapped swap msg
-pub fn create_swap_msg(offer_coin: Coin, ask_denom: String) -> CosmosMsg<TerraMsgWrapper> {
-    TerraMsgWrapper {
-        route: TerraRoute::Market,
-        msg_data: TerraMsg::Swap {
-            offer_coin,
-            ask_denom,
-        },
+impl TerraMsg {
+    /// route returns the module that handles the msg
+    pub fn route(&self) -> TerraRoute {
+        match self {
+            TerraMsg::Swap { .. } | TerraMsg::SwapSend { .. } => TerraRoute::Market,
+        }
     }
+}
+
+// create_swap_msg returns wrapped swap msg
+pub fn create_swap_msg(offer_coin: Coin, ask_denom: Denom) -> CosmosMsg<TerraMsgWrapper> {
+    TerraMsgWrapper::from(TerraMsg::Swap {
+        offer_coin,
+        ask_denom,
+    })
     .into()
 }
 
@@ -49,15 +104,329 @@ pub fn create_swap_msg(offer_coin: Coin, ask_denom: String) -> CosmosMsg<TerraMs
 pub fn create_swap_send_msg(
     to_address: String,
     offer_coin: Coin,
-    ask_denom: String,
+    ask_denom: Denom,
 ) -> CosmosMsg<TerraMsgWrapper> {
-    TerraMsgWrapper {
-        route: TerraRoute::Market,
-        msg_data: TerraMsg::SwapSend {
-            to_address,
-            offer_coin,
-            ask_denom,
-        },
-    }
+    TerraMsgWrapper::from(TerraMsg::SwapSend {
+        to_address,
+        offer_coin,
+        ask_denom,
+    })
     .into()
 }
+
+/// SwapLimit bounds the result of a market swap
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SwapLimit {
+    /// Minimum amount of the ask denom to receive
+    MinReceive(Uint128),
+    /// Maximum spread between the oracle exchange rate and the market swap result
+    MaxSpread(Decimal),
+}
+
+// create_swap_msg_with_limit returns wrapped swap msg, or an error if the swap
+// would currently violate the given limit
+pub fn create_swap_msg_with_limit(
+    querier: &TerraQuerier,
+    offer_coin: Coin,
+    ask_denom: Denom,
+    limit: SwapLimit,
+) -> StdResult<CosmosMsg<TerraMsgWrapper>> {
+    assert_swap_limit(querier, &offer_coin, &ask_denom, limit)?;
+
+    Ok(create_swap_msg(offer_coin, ask_denom))
+}
+
+// create_swap_send_msg_with_limit returns wrapped swap send msg, or an error if the swap
+// would currently violate the given limit
+pub fn create_swap_send_msg_with_limit(
+    querier: &TerraQuerier,
+    to_address: String,
+    offer_coin: Coin,
+    ask_denom: Denom,
+    limit: SwapLimit,
+) -> StdResult<CosmosMsg<TerraMsgWrapper>> {
+    assert_swap_limit(querier, &offer_coin, &ask_denom, limit)?;
+
+    Ok(create_swap_send_msg(to_address, offer_coin, ask_denom))
+}
+
+// create_send_msg_deduct_tax returns a bank send of `amount` less the stability tax on each
+// coin, so that the transfer costs at most `amount` in total
+pub fn create_send_msg_deduct_tax(
+    querier: &TerraQuerier,
+    to_address: String,
+    amount: Vec<Coin>,
+) -> StdResult<CosmosMsg<TerraMsgWrapper>> {
+    create_send_msg(to_address, deduct_taxes(querier, amount)?)
+}
+
+// create_send_msg_gross_up returns a bank send of `amount` plus the stability tax on each
+// coin, so that the recipient receives exactly `amount`
+pub fn create_send_msg_gross_up(
+    querier: &TerraQuerier,
+    to_address: String,
+    amount: Vec<Coin>,
+) -> StdResult<CosmosMsg<TerraMsgWrapper>> {
+    create_send_msg(to_address, add_taxes(querier, amount)?)
+}
+
+// the bank module rejects zero coins, so they are left out
+fn create_send_msg(to_address: String, amount: Vec<Coin>) -> StdResult<CosmosMsg<TerraMsgWrapper>> {
+    let amount: Vec<Coin> = amount
+        .into_iter()
+        .filter(|coin| !coin.amount.is_zero())
+        .collect();
+    if amount.is_empty() {
+        return Err(StdError::generic_err(format!(
+            "No coins to send to {}",
+            to_address
+        )));
+    }
+
+    Ok(BankMsg::Send { to_address, amount }.into())
+}
+
+fn assert_swap_limit(
+    querier: &TerraQuerier,
+    offer_coin: &Coin,
+    ask_denom: &Denom,
+    limit: SwapLimit,
+) -> StdResult<()> {
+    let receive = Uint256::from(
+        querier
+            .query_swap(offer_coin.clone(), ask_denom.as_str())?
+            .receive
+            .amount,
+    );
+
+    let min_receive = match limit {
+        SwapLimit::MinReceive(min_receive) => Uint256::from(min_receive),
+        SwapLimit::MaxSpread(max_spread) => {
+            if max_spread >= Decimal::one() {
+                return Ok(());
+            }
+
+            let rate = querier
+                .query_exchange_rate(offer_coin.denom.as_str(), ask_denom.as_str())?
+                .exchange_rate;
+            min_receive_at_spread(offer_coin.amount, rate, max_spread)
+        }
+    };
+
+    if receive < min_receive {
+        return Err(StdError::generic_err(format!(
+            "Swap would return {}{}, less than the minimum of {}{}",
+            receive, ask_denom, min_receive, ask_denom
+        )));
+    }
+
+    Ok(())
+}
+
+// offer_amount * rate * (1 - max_spread), computed in 256 bits as it may not fit in a Uint128
+fn min_receive_at_spread(offer_amount: Uint128, rate: Decimal, max_spread: Decimal) -> Uint256 {
+    Uint256::from(offer_amount)
+        * Decimal256::from(rate)
+        * Decimal256::from(Decimal::one() - max_spread)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::query::MarketParametersResponse;
+    use crate::testing::mock_dependencies_with_terra;
+    use cosmwasm_std::{coin, from_slice, to_vec, QuerierWrapper};
+
+    #[test]
+    fn send_msg_with_tax() {
+        let mut deps = mock_dependencies_with_terra(&[]);
+        deps.querier.update_tax_rate(Decimal::permille(5));
+        deps.querier.update_tax_caps(&[
+            ("uusd", Uint128::from(1_000_000u128)),
+            ("ukrw", Uint128::from(1_000u128)),
+            ("ueur", Uint128::from(1_000u128)),
+        ]);
+        let querier = QuerierWrapper::new(&deps.querier);
+        let terra_querier = TerraQuerier::new(&querier);
+
+        let payout = vec![
+            coin(1_000_000, "uusd"),
+            coin(1_000_000, "ukrw"),
+            coin(1_000_000, "uluna"),
+            coin(0, "ueur"),
+        ];
+        let send = |amount: Vec<Coin>| -> CosmosMsg<TerraMsgWrapper> {
+            BankMsg::Send {
+                to_address: "recipient".to_string(),
+                amount,
+            }
+            .into()
+        };
+
+        // uusd pays 0.5%, the ukrw tax is capped and uluna is exempted
+        assert_eq!(
+            create_send_msg_deduct_tax(&terra_querier, "recipient".to_string(), payout.clone())
+                .unwrap(),
+            send(vec![
+                coin(995_025, "uusd"),
+                coin(999_000, "ukrw"),
+                coin(1_000_000, "uluna"),
+            ])
+        );
+        assert_eq!(
+            create_send_msg_gross_up(&terra_querier, "recipient".to_string(), payout).unwrap(),
+            send(vec![
+                coin(1_005_000, "uusd"),
+                coin(1_001_000, "ukrw"),
+                coin(1_000_000, "uluna"),
+            ])
+        );
+
+        match create_send_msg_gross_up(
+            &terra_querier,
+            "recipient".to_string(),
+            vec![coin(0, "uusd")],
+        )
+        .unwrap_err()
+        {
+            StdError::GenericErr { msg, .. } => assert_eq!(msg, "No coins to send to recipient"),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn msg_wrapper_route() {
+        let msg = create_swap_msg(coin(1_000_000, "uluna"), Denom::new("uusd").unwrap());
+        let wrapper = match msg {
+            CosmosMsg::Custom(wrapper) => wrapper,
+            _ => panic!("Unexpected msg: {:?}", msg),
+        };
+        assert_eq!(wrapper.route, TerraRoute::Market);
+        assert_eq!(
+            from_slice::<TerraMsgWrapper>(&to_vec(&wrapper).unwrap()).unwrap(),
+            wrapper
+        );
+
+        let err = from_slice::<TerraMsgWrapper>(
+            br#"{"route":"oracle","msg_data":{"swap":{"offer_coin":{"denom":"uluna","amount":"1"},"ask_denom":"uusd"}}}"#,
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Route Oracle does not match msg_data, which belongs to route Market"));
+
+        // misspelled offer and ask denoms
+        let err = from_slice::<TerraMsgWrapper>(
+            br#"{"route":"market","msg_data":{"swap":{"offer_coin":{"denom":"uluna ","amount":"1"},"ask_denom":"uusd"}}}"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Invalid denom 'uluna '"));
+        let err = from_slice::<TerraMsgWrapper>(
+            br#"{"route":"market","msg_data":{"swap":{"offer_coin":{"denom":"uluna","amount":"1"},"ask_denom":"u_sd!"}}}"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Invalid denom 'u_sd!'"));
+    }
+
+    #[test]
+    fn swap_limit_of_huge_offers() {
+        // the oracle value of the offer exceeds Uint128::MAX
+        let rate = Decimal::from_ratio(10u128.pow(15), 1u128);
+        assert_eq!(
+            min_receive_at_spread(Uint128::MAX, rate, Decimal::permille(20)),
+            Uint256::from(Uint128::MAX) * Decimal256::from_ratio(980_000_000_000_000u64, 1u64)
+        );
+        assert_eq!(
+            min_receive_at_spread(Uint128::MAX, rate, Decimal::percent(100)),
+            Uint256::zero()
+        );
+    }
+
+    #[test]
+    fn swap_msg_with_limit() {
+        let mut deps = mock_dependencies_with_terra(&[]);
+        deps.querier.update_exchange_rates(
+            "uluna",
+            &[
+                ("usdr", Decimal::percent(4050)),
+                ("uusd", Decimal::percent(5840)),
+            ],
+        );
+        deps.querier
+            .update_exchange_rates("uusd", &[("uluna", Decimal::from_ratio(100u128, 5840u128))]);
+        deps.querier
+            .update_market_parameters(MarketParametersResponse {
+                base_pool: Decimal::from_ratio(50_000_000_000_000u128, 1u128),
+                pool_recovery_period: 36,
+                min_stability_spread: Decimal::permille(20),
+            });
+
+        let querier = QuerierWrapper::new(&deps.querier);
+        let terra_querier = TerraQuerier::new(&querier);
+
+        // 1000000uluna currently returns 57232000uusd after a spread of 2%
+        let offer = coin(1_000_000, "uluna");
+        let expected = create_swap_msg(offer.clone(), Denom::new("uusd").unwrap());
+        assert_eq!(
+            create_swap_msg_with_limit(
+                &terra_querier,
+                offer.clone(),
+                Denom::new("uusd").unwrap(),
+                SwapLimit::MinReceive(Uint128::from(57_232_000u128)),
+            )
+            .unwrap(),
+            expected
+        );
+        assert_eq!(
+            create_swap_msg_with_limit(
+                &terra_querier,
+                offer.clone(),
+                Denom::new("uusd").unwrap(),
+                SwapLimit::MaxSpread(Decimal::permille(20)),
+            )
+            .unwrap(),
+            expected
+        );
+
+        match create_swap_msg_with_limit(
+            &terra_querier,
+            offer.clone(),
+            Denom::new("uusd").unwrap(),
+            SwapLimit::MinReceive(Uint128::from(57_232_001u128)),
+        )
+        .unwrap_err()
+        {
+            StdError::GenericErr { msg, .. } => assert_eq!(
+                msg,
+                "Swap would return 57232000uusd, less than the minimum of 57232001uusd"
+            ),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+        assert!(create_swap_msg_with_limit(
+            &terra_querier,
+            offer.clone(),
+            Denom::new("uusd").unwrap(),
+            SwapLimit::MaxSpread(Decimal::permille(19)),
+        )
+        .is_err());
+
+        // luna is bought with the same spread
+        assert!(create_swap_send_msg_with_limit(
+            &terra_querier,
+            "recipient0000".to_string(),
+            coin(58_400_000, "uusd"),
+            Denom::new("uluna").unwrap(),
+            SwapLimit::MaxSpread(Decimal::permille(20)),
+        )
+        .is_ok());
+        assert!(create_swap_send_msg_with_limit(
+            &terra_querier,
+            "recipient0000".to_string(),
+            coin(58_400_000, "uusd"),
+            Denom::new("uluna").unwrap(),
+            SwapLimit::MaxSpread(Decimal::permille(19)),
+        )
+        .is_err());
+    }
+}
@@ -1,10 +1,15 @@
-use cosmwasm_std::{Coin, QuerierWrapper, StdResult};
+use cosmwasm_std::{Binary, Coin, QuerierWrapper, StdResult};
 
+use crate::denom::Denom;
+use crate::error::TerraResult;
 use crate::query::{
-    ContractInfoResponse, ExchangeRatesResponse, SwapResponse, TaxCapResponse, TaxRateResponse,
-    TerraQuery, TerraQueryWrapper,
+    ActivesResponse, CodeInfoResponse, ContractInfoResponse, ExchangeRateResponse,
+    ExchangeRatesResponse, FeederDelegationResponse, IndicatorsResponse, MarketParametersResponse,
+    MissCounterResponse, OracleParametersResponse, RawStoreResponse, RewardWeightResponse,
+    SeigniorageProceedsResponse, SwapResponse, TaxCapResponse, TaxProceedsResponse,
+    TaxRateResponse, TerraPoolDeltaResponse, TerraQuery, TerraQueryWrapper, TobinTaxResponse,
+    TobinTaxesResponse, TreasuryParametersResponse, VoteTargetsResponse, WasmParametersResponse,
 };
-use crate::route::TerraRoute;
 
 /// This is a helper wrapper to easily use our custom queries
 pub struct TerraQuerier<'a> {
@@ -20,70 +25,197 @@ impl<'a> TerraQuerier<'a> {
         &self,
         offer_coin: Coin,
         ask_denom: T,
-    ) -> StdResult<SwapResponse> {
-        let request = TerraQueryWrapper {
-            route: TerraRoute::Market,
-            query_data: TerraQuery::Swap {
-                offer_coin,
-                ask_denom: ask_denom.into(),
-            },
-        }
+    ) -> TerraResult<SwapResponse> {
+        Denom::new(offer_coin.denom.as_str())?;
+        let request = TerraQueryWrapper::from(TerraQuery::Swap {
+            offer_coin,
+            ask_denom: Denom::new(ask_denom)?,
+        })
         .into();
 
-        self.querier.custom_query(&request)
+        Ok(self.querier.custom_query(&request)?)
     }
 
-    pub fn query_tax_cap<T: Into<String>>(&self, denom: T) -> StdResult<TaxCapResponse> {
-        let request = TerraQueryWrapper {
-            route: TerraRoute::Treasury,
-            query_data: TerraQuery::TaxCap {
-                denom: denom.into(),
-            },
-        }
-        .into();
+    pub fn query_terra_pool_delta(&self) -> TerraResult<TerraPoolDeltaResponse> {
+        let request = TerraQueryWrapper::from(TerraQuery::TerraPoolDelta {}).into();
 
-        self.querier.custom_query(&request)
+        Ok(self.querier.custom_query(&request)?)
     }
 
-    pub fn query_tax_rate(&self) -> StdResult<TaxRateResponse> {
-        let request = TerraQueryWrapper {
-            route: TerraRoute::Treasury,
-            query_data: TerraQuery::TaxRate {},
-        }
+    pub fn query_market_parameters(&self) -> TerraResult<MarketParametersResponse> {
+        let request = TerraQueryWrapper::from(TerraQuery::MarketParameters {}).into();
+
+        Ok(self.querier.custom_query(&request)?)
+    }
+
+    pub fn query_tax_cap<T: Into<String>>(&self, denom: T) -> TerraResult<TaxCapResponse> {
+        let request = TerraQueryWrapper::from(TerraQuery::TaxCap {
+            denom: Denom::new(denom)?,
+        })
         .into();
 
-        self.querier.custom_query(&request)
+        Ok(self.querier.custom_query(&request)?)
+    }
+
+    pub fn query_tax_rate(&self) -> TerraResult<TaxRateResponse> {
+        let request = TerraQueryWrapper::from(TerraQuery::TaxRate {}).into();
+
+        Ok(self.querier.custom_query(&request)?)
+    }
+
+    pub fn query_reward_weight(&self) -> TerraResult<RewardWeightResponse> {
+        let request = TerraQueryWrapper::from(TerraQuery::RewardWeight {}).into();
+
+        Ok(self.querier.custom_query(&request)?)
+    }
+
+    pub fn query_seigniorage_proceeds(&self) -> TerraResult<SeigniorageProceedsResponse> {
+        let request = TerraQueryWrapper::from(TerraQuery::SeigniorageProceeds {}).into();
+
+        Ok(self.querier.custom_query(&request)?)
+    }
+
+    pub fn query_tax_proceeds(&self) -> TerraResult<TaxProceedsResponse> {
+        let request = TerraQueryWrapper::from(TerraQuery::TaxProceeds {}).into();
+
+        Ok(self.querier.custom_query(&request)?)
+    }
+
+    pub fn query_treasury_parameters(&self) -> TerraResult<TreasuryParametersResponse> {
+        let request = TerraQueryWrapper::from(TerraQuery::TreasuryParameters {}).into();
+
+        Ok(self.querier.custom_query(&request)?)
+    }
+
+    pub fn query_indicators(&self) -> TerraResult<IndicatorsResponse> {
+        let request = TerraQueryWrapper::from(TerraQuery::Indicators {}).into();
+
+        Ok(self.querier.custom_query(&request)?)
     }
 
     pub fn query_exchange_rates<T: Into<String>>(
         &self,
         base_denom: T,
         quote_denoms: Vec<T>,
-    ) -> StdResult<ExchangeRatesResponse> {
-        let request = TerraQueryWrapper {
-            route: TerraRoute::Oracle,
-            query_data: TerraQuery::ExchangeRates {
-                base_denom: base_denom.into(),
-                quote_denoms: quote_denoms.into_iter().map(|x| x.into()).collect(),
-            },
-        }
+    ) -> TerraResult<ExchangeRatesResponse> {
+        let request = TerraQueryWrapper::from(TerraQuery::ExchangeRates {
+            base_denom: Denom::new(base_denom)?,
+            quote_denoms: quote_denoms
+                .into_iter()
+                .map(Denom::new)
+                .collect::<StdResult<_>>()?,
+        })
+        .into();
+
+        Ok(self.querier.custom_query(&request)?)
+    }
+
+    pub fn query_exchange_rate<T: Into<String>>(
+        &self,
+        base_denom: T,
+        quote_denom: T,
+    ) -> TerraResult<ExchangeRateResponse> {
+        let request = TerraQueryWrapper::from(TerraQuery::ExchangeRate {
+            base_denom: Denom::new(base_denom)?,
+            quote_denom: Denom::new(quote_denom)?,
+        })
+        .into();
+
+        Ok(self.querier.custom_query(&request)?)
+    }
+
+    pub fn query_actives(&self) -> TerraResult<ActivesResponse> {
+        let request = TerraQueryWrapper::from(TerraQuery::Actives {}).into();
+
+        Ok(self.querier.custom_query(&request)?)
+    }
+
+    pub fn query_vote_targets(&self) -> TerraResult<VoteTargetsResponse> {
+        let request = TerraQueryWrapper::from(TerraQuery::VoteTargets {}).into();
+
+        Ok(self.querier.custom_query(&request)?)
+    }
+
+    pub fn query_tobin_tax<T: Into<String>>(&self, denom: T) -> TerraResult<TobinTaxResponse> {
+        let request = TerraQueryWrapper::from(TerraQuery::TobinTax {
+            denom: Denom::new(denom)?,
+        })
         .into();
 
-        self.querier.custom_query(&request)
+        Ok(self.querier.custom_query(&request)?)
+    }
+
+    pub fn query_tobin_taxes(&self) -> TerraResult<TobinTaxesResponse> {
+        let request = TerraQueryWrapper::from(TerraQuery::TobinTaxes {}).into();
+
+        Ok(self.querier.custom_query(&request)?)
+    }
+
+    pub fn query_oracle_parameters(&self) -> TerraResult<OracleParametersResponse> {
+        let request = TerraQueryWrapper::from(TerraQuery::OracleParameters {}).into();
+
+        Ok(self.querier.custom_query(&request)?)
+    }
+
+    pub fn query_miss_counter<T: Into<String>>(
+        &self,
+        validator: T,
+    ) -> TerraResult<MissCounterResponse> {
+        let request = TerraQueryWrapper::from(TerraQuery::MissCounter {
+            validator: validator.into(),
+        })
+        .into();
+
+        Ok(self.querier.custom_query(&request)?)
+    }
+
+    pub fn query_feeder_delegation<T: Into<String>>(
+        &self,
+        validator: T,
+    ) -> TerraResult<FeederDelegationResponse> {
+        let request = TerraQueryWrapper::from(TerraQuery::FeederDelegation {
+            validator: validator.into(),
+        })
+        .into();
+
+        Ok(self.querier.custom_query(&request)?)
     }
 
     pub fn query_contract_info<T: Into<String>>(
         &self,
         contract_address: T,
-    ) -> StdResult<ContractInfoResponse> {
-        let request = TerraQueryWrapper {
-            route: TerraRoute::Wasm,
-            query_data: TerraQuery::ContractInfo {
-                contract_address: contract_address.into(),
-            },
-        }
+    ) -> TerraResult<ContractInfoResponse> {
+        let request = TerraQueryWrapper::from(TerraQuery::ContractInfo {
+            contract_address: contract_address.into(),
+        })
+        .into();
+
+        Ok(self.querier.custom_query(&request)?)
+    }
+
+    pub fn query_code_info(&self, code_id: u64) -> TerraResult<CodeInfoResponse> {
+        let request = TerraQueryWrapper::from(TerraQuery::CodeInfo { code_id }).into();
+
+        Ok(self.querier.custom_query(&request)?)
+    }
+
+    pub fn query_raw_store<T: Into<String>>(
+        &self,
+        contract_address: T,
+        key: Binary,
+    ) -> TerraResult<RawStoreResponse> {
+        let request = TerraQueryWrapper::from(TerraQuery::RawStore {
+            contract_address: contract_address.into(),
+            key,
+        })
         .into();
 
-        self.querier.custom_query(&request)
+        Ok(self.querier.custom_query(&request)?)
+    }
+
+    pub fn query_wasm_parameters(&self) -> TerraResult<WasmParametersResponse> {
+        let request = TerraQueryWrapper::from(TerraQuery::WasmParameters {}).into();
+
+        Ok(self.querier.custom_query(&request)?)
     }
 }
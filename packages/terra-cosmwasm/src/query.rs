@@ -1,12 +1,16 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
 
+use crate::denom::Denom;
 use crate::route::TerraRoute;
-use cosmwasm_std::{Coin, CustomQuery, Decimal, Uint128};
+use cosmwasm_bignumber::SignedDecimal256;
+use cosmwasm_std::{Binary, Coin, CustomQuery, Decimal, Uint128};
 
-/// TerraQueryWrapper is an override of QueryRequest::Custom to access Terra-specific modules
+/// TerraQueryWrapper is an override of QueryRequest::Custom to access Terra-specific modules.
+/// Deserialization fails when `route` is not the route of `query_data`.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-#[serde(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case", try_from = "UncheckedTerraQueryWrapper")]
 pub struct TerraQueryWrapper {
     pub route: TerraRoute,
     pub query_data: TerraQuery,
@@ -15,25 +19,129 @@ pub struct TerraQueryWrapper {
 // implement custom query
 impl CustomQuery for TerraQueryWrapper {}
 
+// wraps the query with the route it belongs to
+impl From<TerraQuery> for TerraQueryWrapper {
+    fn from(query_data: TerraQuery) -> Self {
+        TerraQueryWrapper {
+            route: query_data.route(),
+            query_data,
+        }
+    }
+}
+
+/// TerraQueryWrapper as received, before its route is checked against its query
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+struct UncheckedTerraQueryWrapper {
+    route: TerraRoute,
+    query_data: TerraQuery,
+}
+
+impl TryFrom<UncheckedTerraQueryWrapper> for TerraQueryWrapper {
+    type Error = String;
+
+    fn try_from(unchecked: UncheckedTerraQueryWrapper) -> Result<Self, Self::Error> {
+        let expected = unchecked.query_data.route();
+        if unchecked.route != expected {
+            return Err(format!(
+                "Route {:?} does not match query_data, which belongs to route {:?}",
+                unchecked.route, expected
+            ));
+        }
+        // Coin does not validate its denom
+        if let TerraQuery::Swap { offer_coin, .. } = &unchecked.query_data {
+            Denom::new(offer_coin.denom.as_str()).map_err(|e| e.to_string())?;
+        }
+
+        Ok(TerraQueryWrapper {
+            route: unchecked.route,
+            query_data: unchecked.query_data,
+        })
+    }
+}
+
 /// TerraQuery is defines available query datas
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum TerraQuery {
     Swap {
         offer_coin: Coin,
-        ask_denom: String,
+        ask_denom: Denom,
     },
+    TerraPoolDelta {},
+    MarketParameters {},
     TaxRate {},
     TaxCap {
-        denom: String,
+        denom: Denom,
     },
+    RewardWeight {},
+    SeigniorageProceeds {},
+    TaxProceeds {},
+    TreasuryParameters {},
+    Indicators {},
     ExchangeRates {
-        base_denom: String,
-        quote_denoms: Vec<String>,
+        base_denom: Denom,
+        quote_denoms: Vec<Denom>,
+    },
+    ExchangeRate {
+        base_denom: Denom,
+        quote_denom: Denom,
+    },
+    Actives {},
+    VoteTargets {},
+    TobinTax {
+        denom: Denom,
+    },
+    TobinTaxes {},
+    OracleParameters {},
+    MissCounter {
+        validator: String,
+    },
+    FeederDelegation {
+        validator: String,
     },
     ContractInfo {
         contract_address: String,
     },
+    CodeInfo {
+        code_id: u64,
+    },
+    RawStore {
+        contract_address: String,
+        key: Binary,
+    },
+    WasmParameters {},
+}
+
+impl TerraQuery {
+    /// route returns the module that answers the query
+    pub fn route(&self) -> TerraRoute {
+        match self {
+            TerraQuery::Swap { .. }
+            | TerraQuery::TerraPoolDelta {}
+            | TerraQuery::MarketParameters {} => TerraRoute::Market,
+            TerraQuery::TaxRate {}
+            | TerraQuery::TaxCap { .. }
+            | TerraQuery::RewardWeight {}
+            | TerraQuery::SeigniorageProceeds {}
+            | TerraQuery::TaxProceeds {}
+            | TerraQuery::TreasuryParameters {}
+            | TerraQuery::Indicators {} => TerraRoute::Treasury,
+            TerraQuery::ExchangeRates { .. }
+            | TerraQuery::ExchangeRate { .. }
+            | TerraQuery::Actives {}
+            | TerraQuery::VoteTargets {}
+            | TerraQuery::TobinTax { .. }
+            | TerraQuery::TobinTaxes {}
+            | TerraQuery::OracleParameters {}
+            | TerraQuery::MissCounter { .. }
+            | TerraQuery::FeederDelegation { .. } => TerraRoute::Oracle,
+            TerraQuery::ContractInfo { .. }
+            | TerraQuery::CodeInfo { .. }
+            | TerraQuery::RawStore { .. }
+            | TerraQuery::WasmParameters {} => TerraRoute::Wasm,
+        }
+    }
 }
 
 /// SwapResponse is data format returned from SwapRequest::Simulate query
@@ -42,6 +150,20 @@ pub struct SwapResponse {
     pub receive: Coin,
 }
 
+/// TerraPoolDeltaResponse is data format returned from MarketRequest::TerraPoolDelta query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TerraPoolDeltaResponse {
+    pub terra_pool_delta: SignedDecimal256,
+}
+
+/// MarketParametersResponse is data format returned from MarketRequest::Parameters query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MarketParametersResponse {
+    pub base_pool: Decimal,
+    pub pool_recovery_period: u64,
+    pub min_stability_spread: Decimal,
+}
+
 /// TaxRateResponse is data format returned from TreasuryRequest::TaxRate query
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct TaxRateResponse {
@@ -54,6 +176,52 @@ pub struct TaxCapResponse {
     pub cap: Uint128,
 }
 
+/// RewardWeightResponse is data format returned from TreasuryRequest::RewardWeight query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardWeightResponse {
+    pub reward_weight: Decimal,
+}
+
+/// SeigniorageProceedsResponse is data format returned from TreasuryRequest::SeigniorageProceeds query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SeigniorageProceedsResponse {
+    pub seigniorage_proceeds: Uint128,
+}
+
+/// TaxProceedsResponse is data format returned from TreasuryRequest::TaxProceeds query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TaxProceedsResponse {
+    pub tax_proceeds: Vec<Coin>,
+}
+
+/// PolicyConstraints is data format returned from TreasuryRequest::Parameters query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PolicyConstraints {
+    pub rate_min: Decimal,
+    pub rate_max: Decimal,
+    pub cap: Coin,
+    pub change_rate_max: Decimal,
+}
+
+/// TreasuryParametersResponse is data format returned from TreasuryRequest::Parameters query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TreasuryParametersResponse {
+    pub tax_policy: PolicyConstraints,
+    pub reward_policy: PolicyConstraints,
+    pub seigniorage_burden_target: Decimal,
+    pub mining_increment: Decimal,
+    pub window_short: u64,
+    pub window_long: u64,
+    pub window_probation: u64,
+}
+
+/// IndicatorsResponse is data format returned from TreasuryRequest::Indicators query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IndicatorsResponse {
+    pub trl_year: Decimal,
+    pub trl_month: Decimal,
+}
+
 /// ExchangeRateItem is data format returned from OracleRequest::ExchangeRates query
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ExchangeRateItem {
@@ -68,6 +236,70 @@ pub struct ExchangeRatesResponse {
     pub exchange_rates: Vec<ExchangeRateItem>,
 }
 
+/// ExchangeRateResponse is data format returned from OracleRequest::ExchangeRate query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExchangeRateResponse {
+    pub base_denom: String,
+    pub quote_denom: String,
+    pub exchange_rate: Decimal,
+}
+
+/// ActivesResponse is data format returned from OracleRequest::Actives query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ActivesResponse {
+    pub actives: Vec<String>,
+}
+
+/// VoteTargetsResponse is data format returned from OracleRequest::VoteTargets query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VoteTargetsResponse {
+    pub vote_targets: Vec<String>,
+}
+
+/// TobinTaxResponse is data format returned from OracleRequest::TobinTax query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TobinTaxResponse {
+    pub rate: Decimal,
+}
+
+/// TobinTaxItem is data format returned from OracleRequest::TobinTaxes and OracleRequest::Parameters queries
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TobinTaxItem {
+    pub denom: String,
+    pub tobin_tax: Decimal,
+}
+
+/// TobinTaxesResponse is data format returned from OracleRequest::TobinTaxes query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TobinTaxesResponse {
+    pub tobin_taxes: Vec<TobinTaxItem>,
+}
+
+/// OracleParametersResponse is data format returned from OracleRequest::Parameters query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OracleParametersResponse {
+    pub vote_period: u64,
+    pub vote_threshold: Decimal,
+    pub reward_band: Decimal,
+    pub reward_distribution_window: u64,
+    pub whitelist: Vec<TobinTaxItem>,
+    pub slash_fraction: Decimal,
+    pub slash_window: u64,
+    pub min_valid_per_window: Decimal,
+}
+
+/// MissCounterResponse is data format returned from OracleRequest::MissCounter query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MissCounterResponse {
+    pub miss_counter: u64,
+}
+
+/// FeederDelegationResponse is data format returned from OracleRequest::FeederDelegation query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeederDelegationResponse {
+    pub feeder: String,
+}
+
 /// ContractInfoResponse is data format returned from WasmRequest::ContractInfo query
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ContractInfoResponse {
@@ -76,3 +308,96 @@ pub struct ContractInfoResponse {
     pub code_id: u64,
     pub admin: Option<String>,
 }
+
+/// CodeInfoResponse is data format returned from WasmRequest::CodeInfo query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CodeInfoResponse {
+    pub code_id: u64,
+    pub creator: String,
+    pub code_hash: Binary,
+}
+
+/// RawStoreResponse is data format returned from WasmRequest::RawStore query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RawStoreResponse {
+    /// Raw value stored under the requested key, empty if the key is not set
+    pub data: Binary,
+}
+
+/// WasmParametersResponse is data format returned from WasmRequest::Parameters query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WasmParametersResponse {
+    pub max_contract_size: u64,
+    pub max_contract_gas: u64,
+    pub max_contract_msg_size: u64,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cosmwasm_std::{from_slice, to_vec};
+
+    #[test]
+    fn wrapper_takes_the_route_of_the_query() {
+        let wrapper = TerraQueryWrapper::from(TerraQuery::TaxRate {});
+        assert_eq!(wrapper.route, TerraRoute::Treasury);
+
+        let wrapper = TerraQueryWrapper::from(TerraQuery::ExchangeRate {
+            base_denom: Denom::new("uluna").unwrap(),
+            quote_denom: Denom::new("uusd").unwrap(),
+        });
+        assert_eq!(wrapper.route, TerraRoute::Oracle);
+        assert_eq!(
+            from_slice::<TerraQueryWrapper>(&to_vec(&wrapper).unwrap()).unwrap(),
+            wrapper
+        );
+
+        assert_eq!(
+            TerraQuery::Swap {
+                offer_coin: cosmwasm_std::coin(1, "uluna"),
+                ask_denom: Denom::new("uusd").unwrap(),
+            }
+            .route(),
+            TerraRoute::Market
+        );
+        assert_eq!(
+            TerraQuery::CodeInfo { code_id: 1 }.route(),
+            TerraRoute::Wasm
+        );
+    }
+
+    #[test]
+    fn wrapper_rejects_mismatched_route() {
+        let err =
+            from_slice::<TerraQueryWrapper>(br#"{"route":"oracle","query_data":{"tax_rate":{}}}"#)
+                .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Route Oracle does not match query_data, which belongs to route Treasury"));
+
+        let wrapper = from_slice::<TerraQueryWrapper>(
+            br#"{"route":"treasury","query_data":{"tax_rate":{}}}"#,
+        )
+        .unwrap();
+        assert_eq!(wrapper, TerraQueryWrapper::from(TerraQuery::TaxRate {}));
+    }
+
+    #[test]
+    fn wrapper_rejects_misspelled_denoms() {
+        let err = from_slice::<TerraQueryWrapper>(
+            br#"{"route":"treasury","query_data":{"tax_cap":{"denom":"u usd"}}}"#,
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Invalid denom 'u usd': may only contain letters, digits and /:._-"));
+
+        let err = from_slice::<TerraQueryWrapper>(
+            br#"{"route":"market","query_data":{"swap":{"offer_coin":{"denom":"1uluna","amount":"1"},"ask_denom":"uusd"}}}"#,
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Invalid denom '1uluna': must start with a letter"));
+    }
+}
@@ -0,0 +1,192 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use cosmwasm_std::{Coin, ContractResult, Decimal, Event, Reply, StdError, StdResult, Uint128};
+
+/// Type of the event emitted by the market module for every executed swap
+const SWAP_EVENT_TYPE: &str = "swap";
+
+/// DecCoin is a coin with a decimal amount, as used by the chain for fees
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DecCoin {
+    pub denom: String,
+    pub amount: Decimal,
+}
+
+/// SwapResult is the outcome of a `TerraMsg::Swap` or `TerraMsg::SwapSend`,
+/// as reported by the market module's `swap` event
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SwapResult {
+    /// Coin given up by the trader
+    pub offer: Coin,
+    pub trader: String,
+    /// Address that received `swap_coin`; the trader itself for a plain swap
+    pub recipient: String,
+    /// Coin received, after deduction of the spread fee
+    pub swap_coin: Coin,
+    /// Spread fee charged in the ask denom
+    pub swap_fee: DecCoin,
+}
+
+// parse_swap_reply returns the result of the first swap executed by a submessage
+pub fn parse_swap_reply(reply: &Reply) -> StdResult<SwapResult> {
+    let response = match &reply.result {
+        ContractResult::Ok(response) => response,
+        ContractResult::Err(err) => {
+            return Err(StdError::generic_err(format!(
+                "Swap submessage {} failed: {}",
+                reply.id, err
+            )))
+        }
+    };
+
+    let event = response
+        .events
+        .iter()
+        .find(|event| event.ty == SWAP_EVENT_TYPE)
+        .ok_or_else(|| {
+            StdError::generic_err(format!("No swap event in reply to submessage {}", reply.id))
+        })?;
+
+    Ok(SwapResult {
+        offer: parse_coin(event_attribute(event, "offer")?)?,
+        trader: event_attribute(event, "trader")?.to_string(),
+        recipient: event_attribute(event, "recipient")?.to_string(),
+        swap_coin: parse_coin(event_attribute(event, "swap_coin")?)?,
+        swap_fee: parse_dec_coin(event_attribute(event, "swap_fee")?)?,
+    })
+}
+
+fn event_attribute<'a>(event: &'a Event, key: &str) -> StdResult<&'a str> {
+    event
+        .attributes
+        .iter()
+        .find(|attr| attr.key == key)
+        .map(|attr| attr.value.as_str())
+        .ok_or_else(|| StdError::generic_err(format!("Missing {} attribute in swap event", key)))
+}
+
+/// Splits a chain coin string such as "1000uusd" into its amount and denom
+fn split_coin(input: &str) -> StdResult<(&str, &str)> {
+    match input.find(|c: char| c.is_ascii_alphabetic()) {
+        Some(i) if i > 0 => Ok(input.split_at(i)),
+        _ => Err(StdError::generic_err(format!("Invalid coin: {}", input))),
+    }
+}
+
+fn parse_coin(input: &str) -> StdResult<Coin> {
+    let (amount, denom) = split_coin(input)?;
+    let amount = amount
+        .parse::<u128>()
+        .map_err(|_| StdError::generic_err(format!("Invalid coin: {}", input)))?;
+
+    Ok(Coin {
+        denom: denom.to_string(),
+        amount: Uint128::from(amount),
+    })
+}
+
+fn parse_dec_coin(input: &str) -> StdResult<DecCoin> {
+    let (amount, denom) = split_coin(input)?;
+
+    Ok(DecCoin {
+        denom: denom.to_string(),
+        amount: Decimal::from_str(amount)?,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cosmwasm_std::{coin, SubMsgExecutionResponse};
+
+    fn reply_with_events(events: Vec<Event>) -> Reply {
+        Reply {
+            id: 7,
+            result: ContractResult::Ok(SubMsgExecutionResponse { events, data: None }),
+        }
+    }
+
+    // Events of a swap send of 1000000uusd to ukrw. The event types, attribute keys and their
+    // order follow handleSwapRequest in Terra Core's x/market/keeper/msg_server.go, which emits
+    // the swap event followed by a message event. The amounts are what simulate_swap computes
+    // for the mock market of the market tests, with swap_fee before truncation.
+    // TODO: replace with the events of a recorded MsgSwapSend and cite its tx hash and network
+    fn swap_send_events() -> Vec<Event> {
+        vec![
+            Event::new("coin_spent").add_attributes(vec![
+                ("spender", "terra18vd8fpwxzck93qlwghaj6arh4ch8hhcjx2qxtq"),
+                ("amount", "1000000uusd"),
+            ]),
+            Event::new("swap").add_attributes(vec![
+                ("offer", "1000000uusd"),
+                ("trader", "terra18vd8fpwxzck93qlwghaj6arh4ch8hhcjx2qxtq"),
+                ("recipient", "terra1qnx5w2w4h56vwx4r9q6n2h6zsqchqa7jdpwzkf"),
+                ("swap_coin", "1098026241ukrw"),
+                ("swap_fee", "39824785.958904109589041092ukrw"),
+            ]),
+            Event::new("message").add_attributes(vec![
+                ("module", "market"),
+                ("sender", "terra18vd8fpwxzck93qlwghaj6arh4ch8hhcjx2qxtq"),
+            ]),
+        ]
+    }
+
+    #[test]
+    fn parse_swap_send_reply() {
+        let result = parse_swap_reply(&reply_with_events(swap_send_events())).unwrap();
+        assert_eq!(
+            result,
+            SwapResult {
+                offer: coin(1_000_000, "uusd"),
+                trader: "terra18vd8fpwxzck93qlwghaj6arh4ch8hhcjx2qxtq".to_string(),
+                recipient: "terra1qnx5w2w4h56vwx4r9q6n2h6zsqchqa7jdpwzkf".to_string(),
+                swap_coin: coin(1_098_026_241, "ukrw"),
+                swap_fee: DecCoin {
+                    denom: "ukrw".to_string(),
+                    amount: Decimal::from_str("39824785.958904109589041092").unwrap(),
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn parse_swap_reply_errors() {
+        let failed = Reply {
+            id: 7,
+            result: ContractResult::Err("insufficient funds".to_string()),
+        };
+        match parse_swap_reply(&failed).unwrap_err() {
+            StdError::GenericErr { msg, .. } => {
+                assert_eq!(msg, "Swap submessage 7 failed: insufficient funds")
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+
+        let mut events = swap_send_events();
+        events.retain(|event| event.ty != "swap");
+        match parse_swap_reply(&reply_with_events(events)).unwrap_err() {
+            StdError::GenericErr { msg, .. } => {
+                assert_eq!(msg, "No swap event in reply to submessage 7")
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+
+        let mut events = swap_send_events();
+        events[1].attributes.retain(|attr| attr.key != "swap_coin");
+        match parse_swap_reply(&reply_with_events(events)).unwrap_err() {
+            StdError::GenericErr { msg, .. } => {
+                assert_eq!(msg, "Missing swap_coin attribute in swap event")
+            }
+            e => panic!("Unexpected error: {:?}", e),
+        }
+
+        let mut events = swap_send_events();
+        events[1].attributes[3].value = "ukrw".to_string();
+        match parse_swap_reply(&reply_with_events(events)).unwrap_err() {
+            StdError::GenericErr { msg, .. } => assert_eq!(msg, "Invalid coin: ukrw"),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+}
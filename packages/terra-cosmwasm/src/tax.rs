@@ -0,0 +1,236 @@
+use cosmwasm_std::{Coin, Decimal, Fraction, StdResult, Uint128};
+
+use crate::querier::TerraQuerier;
+
+/// Luna is the only native denom that is exempted from the stability tax
+const TAX_EXEMPT_DENOM: &str = "uluna";
+
+/// compute_tax returns the part of `coin` that the treasury will charge as stability tax
+/// when `coin.amount` is the whole budget of a transfer, i.e. the amount sent plus its tax.
+/// The remaining `coin.amount - tax` is the largest amount that can be sent with that budget.
+pub fn compute_tax(querier: &TerraQuerier, coin: &Coin) -> StdResult<Uint128> {
+    if coin.denom == TAX_EXEMPT_DENOM {
+        return Ok(Uint128::zero());
+    }
+
+    let rate = querier.query_tax_rate()?.rate;
+    let cap = querier.query_tax_cap(coin.denom.to_string())?.cap;
+
+    Ok(coin.amount - max_sendable_amount(coin.amount, rate, cap))
+}
+
+/// deduct_tax returns `coin` reduced by the tax charged on it, so that sending the
+/// returned coin costs at most the original `coin.amount`.
+pub fn deduct_tax(querier: &TerraQuerier, coin: Coin) -> StdResult<Coin> {
+    let tax = compute_tax(querier, &coin)?;
+
+    Ok(Coin {
+        denom: coin.denom,
+        amount: coin.amount - tax,
+    })
+}
+
+/// add_tax returns `coin` increased by the tax charged on it, so that the recipient
+/// of a transfer receives exactly the original `coin.amount`. Fails with an overflow
+/// error when the sum exceeds Uint128::MAX.
+pub fn add_tax(querier: &TerraQuerier, coin: Coin) -> StdResult<Coin> {
+    if coin.denom == TAX_EXEMPT_DENOM {
+        return Ok(coin);
+    }
+
+    let rate = querier.query_tax_rate()?.rate;
+    let cap = querier.query_tax_cap(coin.denom.to_string())?.cap;
+
+    Ok(Coin {
+        amount: gross_up(coin.amount, rate, cap)?,
+        denom: coin.denom,
+    })
+}
+
+/// deduct_taxes is deduct_tax for several coins, querying the tax rate only once
+pub(crate) fn deduct_taxes(querier: &TerraQuerier, coins: Vec<Coin>) -> StdResult<Vec<Coin>> {
+    map_taxed_amounts(querier, coins, |amount, rate, cap| {
+        Ok(max_sendable_amount(amount, rate, cap))
+    })
+}
+
+/// add_taxes is add_tax for several coins, querying the tax rate only once
+pub(crate) fn add_taxes(querier: &TerraQuerier, coins: Vec<Coin>) -> StdResult<Vec<Coin>> {
+    map_taxed_amounts(querier, coins, gross_up)
+}
+
+/// Replaces the amount of every taxed coin with f(amount, rate, cap)
+fn map_taxed_amounts<F>(querier: &TerraQuerier, coins: Vec<Coin>, f: F) -> StdResult<Vec<Coin>>
+where
+    F: Fn(Uint128, Decimal, Uint128) -> StdResult<Uint128>,
+{
+    // the rate is only queried when a coin is taxed
+    if coins.iter().all(|coin| coin.denom == TAX_EXEMPT_DENOM) {
+        return Ok(coins);
+    }
+    let rate = querier.query_tax_rate()?.rate;
+
+    let mut res = Vec::with_capacity(coins.len());
+    for coin in coins {
+        if coin.denom == TAX_EXEMPT_DENOM {
+            res.push(coin);
+            continue;
+        }
+
+        let cap = querier.query_tax_cap(coin.denom.to_string())?.cap;
+        res.push(Coin {
+            amount: f(coin.amount, rate, cap)?,
+            denom: coin.denom,
+        });
+    }
+
+    Ok(res)
+}
+
+/// Tax charged by Terra core for sending `amount`: min(floor(amount * rate), cap)
+fn tax_due(amount: Uint128, rate: Decimal, cap: Uint128) -> Uint128 {
+    std::cmp::min(amount * rate, cap)
+}
+
+/// `amount + tax_due(amount)`, or an overflow error
+fn gross_up(amount: Uint128, rate: Decimal, cap: Uint128) -> StdResult<Uint128> {
+    Ok(amount.checked_add(tax_due(amount, rate, cap))?)
+}
+
+/// Largest `net` such that `net + tax_due(net) <= amount`
+fn max_sendable_amount(amount: Uint128, rate: Decimal, cap: Uint128) -> Uint128 {
+    // floor(amount / (1 + rate)) never exceeds the budget, but may be one short of
+    // the optimum because tax_due truncates. A gross amount above Uint128::MAX
+    // exceeds any budget.
+    let mut net = amount.multiply_ratio(rate.denominator(), rate.denominator() + rate.numerator());
+    if let Ok(next) = net.checked_add(Uint128::from(1u128)) {
+        if matches!(gross_up(next, rate, cap), Ok(gross) if gross <= amount) {
+            net = next;
+        }
+    }
+
+    // once the cap is reached, everything above it can be sent
+    std::cmp::max(net, amount.saturating_sub(cap))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing::mock_dependencies_with_terra;
+    use cosmwasm_std::{coin, QuerierWrapper, StdError};
+
+    #[test]
+    fn tax_helpers() {
+        let mut deps = mock_dependencies_with_terra(&[]);
+        deps.querier.update_tax_rate(Decimal::permille(5));
+        deps.querier
+            .update_tax_caps(&[("uusd", Uint128::from(1_000_000u128))]);
+
+        let querier = QuerierWrapper::new(&deps.querier);
+        let terra_querier = TerraQuerier::new(&querier);
+
+        // 995025 + floor(995025 * 0.005) == 1000000
+        assert_eq!(
+            compute_tax(&terra_querier, &coin(1_000_000, "uusd")).unwrap(),
+            Uint128::from(4975u128)
+        );
+        assert_eq!(
+            deduct_tax(&terra_querier, coin(1_000_000, "uusd")).unwrap(),
+            coin(995_025, "uusd")
+        );
+        assert_eq!(
+            add_tax(&terra_querier, coin(995_025, "uusd")).unwrap(),
+            coin(1_000_000, "uusd")
+        );
+        assert_eq!(
+            add_tax(&terra_querier, coin(1_000_000, "uusd")).unwrap(),
+            coin(1_005_000, "uusd")
+        );
+
+        // truncated tax of tiny amounts is zero
+        assert_eq!(
+            deduct_tax(&terra_querier, coin(199, "uusd")).unwrap(),
+            coin(199, "uusd")
+        );
+        assert_eq!(
+            deduct_tax(&terra_querier, coin(201, "uusd")).unwrap(),
+            coin(200, "uusd")
+        );
+
+        // tax is capped
+        assert_eq!(
+            compute_tax(&terra_querier, &coin(1_000_000_000_000, "uusd")).unwrap(),
+            Uint128::from(1_000_000u128)
+        );
+        assert_eq!(
+            add_tax(&terra_querier, coin(1_000_000_000_000, "uusd")).unwrap(),
+            coin(1_000_001_000_000, "uusd")
+        );
+
+        // luna is exempted
+        assert_eq!(
+            compute_tax(&terra_querier, &coin(1_000_000, "uluna")).unwrap(),
+            Uint128::zero()
+        );
+        assert_eq!(
+            add_tax(&terra_querier, coin(1_000_000, "uluna")).unwrap(),
+            coin(1_000_000, "uluna")
+        );
+    }
+
+    #[test]
+    fn deducted_amount_is_optimal() {
+        let rate = Decimal::permille(3);
+        let cap = Uint128::from(4_000u128);
+        for amount in (0u128..2_000_000).step_by(997) {
+            let amount = Uint128::from(amount);
+            let net = max_sendable_amount(amount, rate, cap);
+            let next = net + Uint128::from(1u128);
+
+            assert!(net + tax_due(net, rate, cap) <= amount);
+            assert!(next + tax_due(next, rate, cap) > amount);
+        }
+    }
+
+    #[test]
+    fn tax_near_uint128_max() {
+        let mut deps = mock_dependencies_with_terra(&[]);
+        deps.querier.update_tax_rate(Decimal::permille(5));
+        deps.querier
+            .update_tax_caps(&[("uusd", Uint128::from(1_000_000u128))]);
+
+        let querier = QuerierWrapper::new(&deps.querier);
+        let terra_querier = TerraQuerier::new(&querier);
+
+        let max = Uint128::MAX.u128();
+        assert_eq!(
+            deduct_tax(&terra_querier, coin(max, "uusd")).unwrap(),
+            coin(max - 1_000_000, "uusd")
+        );
+        assert_eq!(
+            add_tax(&terra_querier, coin(max - 1_000_000, "uusd")).unwrap(),
+            coin(max, "uusd")
+        );
+        match add_tax(&terra_querier, coin(max, "uusd")).unwrap_err() {
+            StdError::Overflow { .. } => {}
+            e => panic!("Unexpected error: {:?}", e),
+        }
+        assert!(add_taxes(&terra_querier, vec![coin(max, "uusd")]).is_err());
+        assert_eq!(
+            add_tax(&terra_querier, coin(max, "uluna")).unwrap(),
+            coin(max, "uluna")
+        );
+
+        // a zero rate lets the whole budget be sent
+        assert_eq!(
+            max_sendable_amount(Uint128::MAX, Decimal::zero(), Uint128::zero()),
+            Uint128::MAX
+        );
+        // without a binding cap, the deducted amount is still optimal at the boundary
+        let net = max_sendable_amount(Uint128::MAX, Decimal::permille(5), Uint128::MAX);
+        let next = net + Uint128::from(1u128);
+        let tax = |amount| tax_due(amount, Decimal::permille(5), Uint128::MAX);
+        assert!(net.checked_add(tax(net)).is_ok());
+        assert!(next.checked_add(tax(next)).is_err());
+    }
+}
@@ -0,0 +1,141 @@
+//! Offline conversion between canonical addresses and Terra's bech32 addresses,
+//! for code that has no `deps.api` at hand.
+
+use bech32::{FromBase32, ToBase32, Variant};
+use cosmwasm_std::{Addr, CanonicalAddr, StdError, StdResult};
+
+/// Human readable prefix of account and contract addresses, i.e. `terra1...`
+pub const ACCOUNT_PREFIX: &str = "terra";
+/// Human readable prefix of validator operator addresses, i.e. `terravaloper1...`
+pub const VALIDATOR_PREFIX: &str = "terravaloper";
+
+/// Longest canonical address accepted by the Cosmos SDK
+const MAX_ADDRESS_LENGTH: usize = 255;
+
+/// encode returns the bech32 address of the canonical bytes with the given prefix
+pub fn encode(prefix: &str, canonical: &[u8]) -> StdResult<String> {
+    validate_length(canonical)?;
+    bech32::encode(prefix, canonical.to_base32(), Variant::Bech32)
+        .map_err(|e| StdError::generic_err(format!("Invalid address prefix '{}': {}", prefix, e)))
+}
+
+/// decode checks the checksum of a lowercase bech32 address and returns its prefix
+/// and canonical bytes
+pub fn decode(address: &str) -> StdResult<(String, Vec<u8>)> {
+    let invalid = |reason: String| {
+        StdError::generic_err(format!("Invalid address '{}': {}", address, reason))
+    };
+
+    // bech32 accepts uppercase addresses, but they would not equal the stored ones
+    if address.chars().any(|c| c.is_ascii_uppercase()) {
+        return Err(invalid("must be lowercase".to_string()));
+    }
+
+    let (prefix, data, variant) = bech32::decode(address).map_err(|e| invalid(e.to_string()))?;
+    if variant != Variant::Bech32 {
+        return Err(invalid("must be bech32, not bech32m".to_string()));
+    }
+    let canonical = Vec::<u8>::from_base32(&data).map_err(|e| invalid(e.to_string()))?;
+    validate_length(&canonical).map_err(|e| invalid(e.to_string()))?;
+
+    Ok((prefix, canonical))
+}
+
+/// to_canonical returns the canonical bytes of a `terra1...` address
+pub fn to_canonical(address: &str) -> StdResult<CanonicalAddr> {
+    decode_with_prefix(address, ACCOUNT_PREFIX).map(CanonicalAddr::from)
+}
+
+/// to_human returns the `terra1...` address of canonical bytes
+pub fn to_human(canonical: &CanonicalAddr) -> StdResult<Addr> {
+    encode(ACCOUNT_PREFIX, canonical.as_slice()).map(Addr::unchecked)
+}
+
+/// validate checks a `terra1...` address, like `deps.api.addr_validate` does on chain
+pub fn validate(address: &str) -> StdResult<Addr> {
+    to_canonical(address)?;
+    Ok(Addr::unchecked(address))
+}
+
+/// validate_validator checks a `terravaloper1...` address
+pub fn validate_validator(address: &str) -> StdResult<String> {
+    decode_with_prefix(address, VALIDATOR_PREFIX)?;
+    Ok(address.to_string())
+}
+
+/// to_validator returns the `terravaloper1...` address of the same key as a `terra1...` address
+pub fn to_validator(address: &str) -> StdResult<String> {
+    encode(
+        VALIDATOR_PREFIX,
+        &decode_with_prefix(address, ACCOUNT_PREFIX)?,
+    )
+}
+
+fn decode_with_prefix(address: &str, expected: &str) -> StdResult<Vec<u8>> {
+    let (prefix, canonical) = decode(address)?;
+    if prefix != expected {
+        return Err(StdError::generic_err(format!(
+            "Invalid address '{}': expected prefix '{}', got '{}'",
+            address, expected, prefix
+        )));
+    }
+    Ok(canonical)
+}
+
+fn validate_length(canonical: &[u8]) -> StdResult<()> {
+    if canonical.is_empty() || canonical.len() > MAX_ADDRESS_LENGTH {
+        return Err(StdError::generic_err(format!(
+            "canonical address must be between 1 and {} bytes long",
+            MAX_ADDRESS_LENGTH
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // account and validator operator address of the same 20 canonical bytes
+    const ADDRESS: &str = "terra1x46rqay4d3cssqagxxvqz8xt6nwlz4tdrnwyqh";
+    const VALIDATOR: &str = "terravaloper1x46rqay4d3cssqagxxvqz8xt6nwlz4tdruzesy";
+
+    #[test]
+    fn round_trip() {
+        let canonical = to_canonical(ADDRESS).unwrap();
+        assert_eq!(canonical.len(), 20);
+        assert_eq!(to_human(&canonical).unwrap(), ADDRESS);
+        assert_eq!(validate(ADDRESS).unwrap(), Addr::unchecked(ADDRESS));
+
+        assert_eq!(to_validator(ADDRESS).unwrap(), VALIDATOR);
+        assert_eq!(validate_validator(VALIDATOR).unwrap(), VALIDATOR);
+        assert_eq!(
+            decode(VALIDATOR).unwrap(),
+            (VALIDATOR_PREFIX.to_string(), canonical.to_vec())
+        );
+    }
+
+    #[test]
+    fn validation_errors() {
+        // last character changed
+        let err = validate("terra1x46rqay4d3cssqagxxvqz8xt6nwlz4tdrnwyqj").unwrap_err();
+        assert!(err.to_string().contains("invalid checksum"), "{}", err);
+
+        let err = validate(&ADDRESS.to_uppercase()).unwrap_err();
+        assert!(err.to_string().contains("must be lowercase"), "{}", err);
+
+        match validate(VALIDATOR).unwrap_err() {
+            StdError::GenericErr { msg, .. } => assert_eq!(
+                msg,
+                format!(
+                    "Invalid address '{}': expected prefix 'terra', got 'terravaloper'",
+                    VALIDATOR
+                )
+            ),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+        assert!(validate_validator(ADDRESS).is_err());
+        assert!(validate("creator").is_err());
+        assert!(encode(ACCOUNT_PREFIX, &[]).is_err());
+    }
+}
@@ -0,0 +1,1054 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_slice, to_binary, Addr, Api, CanonicalAddr, Coin, ContractResult, Decimal, OwnedDeps,
+    Querier, QuerierResult, QueryRequest, RecoverPubkeyError, StdResult, SystemError, SystemResult,
+    Uint128, VerificationError,
+};
+
+use crate::market::{simulate_swap, MarketSnapshot};
+use crate::query::{
+    ActivesResponse, CodeInfoResponse, ContractInfoResponse, ExchangeRateItem,
+    ExchangeRateResponse, ExchangeRatesResponse, FeederDelegationResponse, IndicatorsResponse,
+    MarketParametersResponse, MissCounterResponse, OracleParametersResponse, RawStoreResponse,
+    RewardWeightResponse, SeigniorageProceedsResponse, SwapResponse, TaxCapResponse,
+    TaxProceedsResponse, TaxRateResponse, TerraPoolDeltaResponse, TerraQuery, TerraQueryWrapper,
+    TobinTaxItem, TobinTaxResponse, TobinTaxesResponse, TreasuryParametersResponse,
+    VoteTargetsResponse, WasmParametersResponse,
+};
+use crate::route::TerraRoute;
+use crate::terra_address;
+use cosmwasm_bignumber::{Decimal256, SignedDecimal256, Uint256};
+
+/// mock_dependencies_with_terra is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// that answers Terra custom queries from the in-memory tables of a TerraMockQuerier.
+/// It sets the given balance for the contract itself, nothing else
+pub fn mock_dependencies_with_terra(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, TerraMockQuerier> {
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: TerraMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)])),
+    }
+}
+
+/// mock_dependencies_with_terra_api is mock_dependencies_with_terra with a TerraMockApi,
+/// for tests that validate or convert `terra1...` addresses
+pub fn mock_dependencies_with_terra_api(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, TerraMockApi, TerraMockQuerier> {
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: TerraMockApi::default(),
+        querier: TerraMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)])),
+    }
+}
+
+/// mock_terra_address returns a valid `terra1...` address made from the bytes of the label,
+/// zero padded to the 20 bytes of an account address, e.g. mock_terra_address("creator")
+pub fn mock_terra_address(label: &str) -> String {
+    let mut canonical = label.as_bytes().to_vec();
+    canonical.resize(canonical.len().max(20), 0);
+    terra_address::encode(terra_address::ACCOUNT_PREFIX, &canonical).unwrap()
+}
+
+/// TerraMockApi replaces MockApi with the address format of Terra: it only accepts
+/// `terra1...` addresses, and converts them from and to canonical addresses with bech32.
+/// Signature verification is left to MockApi.
+#[derive(Copy, Clone, Default)]
+pub struct TerraMockApi {
+    base: MockApi,
+}
+
+impl Api for TerraMockApi {
+    fn addr_validate(&self, human: &str) -> StdResult<Addr> {
+        terra_address::validate(human)
+    }
+
+    fn addr_canonicalize(&self, human: &str) -> StdResult<CanonicalAddr> {
+        terra_address::to_canonical(human)
+    }
+
+    fn addr_humanize(&self, canonical: &CanonicalAddr) -> StdResult<Addr> {
+        terra_address::to_human(canonical)
+    }
+
+    fn secp256k1_verify(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Result<bool, VerificationError> {
+        self.base
+            .secp256k1_verify(message_hash, signature, public_key)
+    }
+
+    fn secp256k1_recover_pubkey(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        recovery_param: u8,
+    ) -> Result<Vec<u8>, RecoverPubkeyError> {
+        self.base
+            .secp256k1_recover_pubkey(message_hash, signature, recovery_param)
+    }
+
+    fn ed25519_verify(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Result<bool, VerificationError> {
+        self.base.ed25519_verify(message, signature, public_key)
+    }
+
+    fn ed25519_batch_verify(
+        &self,
+        messages: &[&[u8]],
+        signatures: &[&[u8]],
+        public_keys: &[&[u8]],
+    ) -> Result<bool, VerificationError> {
+        self.base
+            .ed25519_batch_verify(messages, signatures, public_keys)
+    }
+
+    fn debug(&self, message: &str) {
+        self.base.debug(message)
+    }
+}
+
+/// TerraMockQuerier wraps the standard MockQuerier and answers every TerraQuery
+/// from configurable tables. Non-custom requests are forwarded to the base querier.
+pub struct TerraMockQuerier {
+    base: MockQuerier<TerraQueryWrapper>,
+    market: MarketQuerier,
+    oracle: OracleQuerier,
+    treasury: TreasuryQuerier,
+    wasm: WasmQuerier,
+}
+
+#[derive(Clone, Default)]
+struct MarketQuerier {
+    terra_pool_delta: SignedDecimal256,
+    parameters: Option<MarketParametersResponse>,
+}
+
+#[derive(Clone, Default)]
+struct OracleQuerier {
+    // base denom => quote denom => amount of quote denom for one unit of base denom
+    exchange_rates: HashMap<String, HashMap<String, Decimal>>,
+    tobin_taxes: HashMap<String, Decimal>,
+    parameters: Option<OracleParametersResponse>,
+    miss_counters: HashMap<String, u64>,
+    feeder_delegations: HashMap<String, String>,
+}
+
+impl OracleQuerier {
+    fn exchange_rate(&self, base_denom: &str, quote_denom: &str) -> Result<Decimal, String> {
+        if base_denom == quote_denom {
+            return Ok(Decimal::one());
+        }
+
+        self.exchange_rates
+            .get(base_denom)
+            .and_then(|rates| rates.get(quote_denom))
+            .copied()
+            .ok_or_else(|| format!("{}: unknown denom", quote_denom))
+    }
+
+    fn tobin_tax(&self, denom: &str) -> Result<Decimal, String> {
+        self.tobin_taxes
+            .get(denom)
+            .copied()
+            .ok_or_else(|| format!("{}: unknown denom", denom))
+    }
+
+    // denoms with a registered exchange rate against luna, like the chain's active denoms
+    fn actives(&self) -> Vec<String> {
+        let mut actives: Vec<String> = self
+            .exchange_rates
+            .get("uluna")
+            .map(|rates| rates.keys().cloned().collect())
+            .unwrap_or_default();
+        actives.sort();
+        actives
+    }
+
+    // the chain votes on every denom with a tobin tax
+    fn vote_targets(&self) -> Vec<String> {
+        let mut vote_targets: Vec<String> = self.tobin_taxes.keys().cloned().collect();
+        vote_targets.sort();
+        vote_targets
+    }
+}
+
+#[derive(Clone, Default)]
+struct TreasuryQuerier {
+    tax_rate: Decimal,
+    tax_caps: HashMap<String, Uint128>,
+    reward_weight: Decimal,
+    seigniorage_proceeds: Uint128,
+    tax_proceeds: Vec<Coin>,
+    parameters: Option<TreasuryParametersResponse>,
+    indicators: Option<IndicatorsResponse>,
+}
+
+impl TreasuryQuerier {
+    // the chain falls back to the cap of its tax policy for denoms without a cap
+    fn tax_cap(&self, denom: &str) -> Result<Uint128, String> {
+        match (self.tax_caps.get(denom), &self.parameters) {
+            (Some(cap), _) => Ok(*cap),
+            (None, Some(params)) => Ok(params.tax_policy.cap.amount),
+            (None, None) => Err(format!(
+                "{}: no tax cap set and treasury parameters not set",
+                denom
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+struct WasmQuerier {
+    contract_infos: HashMap<String, ContractInfoResponse>,
+    code_infos: HashMap<u64, CodeInfoResponse>,
+    // contract address => raw key => raw value
+    raw_stores: HashMap<String, HashMap<Vec<u8>, Vec<u8>>>,
+    parameters: Option<WasmParametersResponse>,
+}
+
+impl Querier for TerraMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<TerraQueryWrapper> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl TerraMockQuerier {
+    pub fn new(base: MockQuerier<TerraQueryWrapper>) -> Self {
+        TerraMockQuerier {
+            base,
+            market: MarketQuerier::default(),
+            oracle: OracleQuerier::default(),
+            treasury: TreasuryQuerier::default(),
+            wasm: WasmQuerier::default(),
+        }
+    }
+
+    pub fn handle_query(&self, request: &QueryRequest<TerraQueryWrapper>) -> QuerierResult {
+        match request {
+            QueryRequest::Custom(TerraQueryWrapper { route, query_data }) => {
+                self.handle_terra_query(route, query_data)
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+
+    fn handle_terra_query(&self, route: &TerraRoute, query_data: &TerraQuery) -> QuerierResult {
+        let res = match (route, query_data) {
+            (
+                TerraRoute::Market,
+                TerraQuery::Swap {
+                    offer_coin,
+                    ask_denom,
+                },
+            ) => self
+                .swap(offer_coin, ask_denom.as_str())
+                .and_then(|res| to_binary(&res).map_err(|e| e.to_string())),
+            (TerraRoute::Market, TerraQuery::TerraPoolDelta {}) => {
+                to_binary(&TerraPoolDeltaResponse {
+                    terra_pool_delta: self.market.terra_pool_delta,
+                })
+                .map_err(|e| e.to_string())
+            }
+            (TerraRoute::Market, TerraQuery::MarketParameters {}) => self
+                .market
+                .parameters
+                .as_ref()
+                .ok_or_else(|| "market parameters not set".to_string())
+                .and_then(|params| to_binary(params).map_err(|e| e.to_string())),
+            (TerraRoute::Treasury, TerraQuery::TaxRate {}) => to_binary(&TaxRateResponse {
+                rate: self.treasury.tax_rate,
+            })
+            .map_err(|e| e.to_string()),
+            (TerraRoute::Treasury, TerraQuery::TaxCap { denom }) => self
+                .treasury
+                .tax_cap(denom.as_str())
+                .and_then(|cap| to_binary(&TaxCapResponse { cap }).map_err(|e| e.to_string())),
+            (TerraRoute::Treasury, TerraQuery::RewardWeight {}) => {
+                to_binary(&RewardWeightResponse {
+                    reward_weight: self.treasury.reward_weight,
+                })
+                .map_err(|e| e.to_string())
+            }
+            (TerraRoute::Treasury, TerraQuery::SeigniorageProceeds {}) => {
+                to_binary(&SeigniorageProceedsResponse {
+                    seigniorage_proceeds: self.treasury.seigniorage_proceeds,
+                })
+                .map_err(|e| e.to_string())
+            }
+            (TerraRoute::Treasury, TerraQuery::TaxProceeds {}) => to_binary(&TaxProceedsResponse {
+                tax_proceeds: self.treasury.tax_proceeds.clone(),
+            })
+            .map_err(|e| e.to_string()),
+            (TerraRoute::Treasury, TerraQuery::TreasuryParameters {}) => self
+                .treasury
+                .parameters
+                .as_ref()
+                .ok_or_else(|| "treasury parameters not set".to_string())
+                .and_then(|params| to_binary(params).map_err(|e| e.to_string())),
+            (TerraRoute::Treasury, TerraQuery::Indicators {}) => self
+                .treasury
+                .indicators
+                .as_ref()
+                .ok_or_else(|| "treasury indicators not set".to_string())
+                .and_then(|indicators| to_binary(indicators).map_err(|e| e.to_string())),
+            (
+                TerraRoute::Oracle,
+                TerraQuery::ExchangeRates {
+                    base_denom,
+                    quote_denoms,
+                },
+            ) => quote_denoms
+                .iter()
+                .map(|quote_denom| {
+                    self.oracle
+                        .exchange_rate(base_denom.as_str(), quote_denom.as_str())
+                        .map(|exchange_rate| ExchangeRateItem {
+                            quote_denom: quote_denom.to_string(),
+                            exchange_rate,
+                        })
+                })
+                .collect::<Result<Vec<ExchangeRateItem>, String>>()
+                .and_then(|exchange_rates| {
+                    to_binary(&ExchangeRatesResponse {
+                        base_denom: base_denom.to_string(),
+                        exchange_rates,
+                    })
+                    .map_err(|e| e.to_string())
+                }),
+            (
+                TerraRoute::Oracle,
+                TerraQuery::ExchangeRate {
+                    base_denom,
+                    quote_denom,
+                },
+            ) => self
+                .oracle
+                .exchange_rate(base_denom.as_str(), quote_denom.as_str())
+                .and_then(|exchange_rate| {
+                    to_binary(&ExchangeRateResponse {
+                        base_denom: base_denom.to_string(),
+                        quote_denom: quote_denom.to_string(),
+                        exchange_rate,
+                    })
+                    .map_err(|e| e.to_string())
+                }),
+            (TerraRoute::Oracle, TerraQuery::Actives {}) => to_binary(&ActivesResponse {
+                actives: self.oracle.actives(),
+            })
+            .map_err(|e| e.to_string()),
+            (TerraRoute::Oracle, TerraQuery::VoteTargets {}) => to_binary(&VoteTargetsResponse {
+                vote_targets: self.oracle.vote_targets(),
+            })
+            .map_err(|e| e.to_string()),
+            (TerraRoute::Oracle, TerraQuery::TobinTax { denom }) => self
+                .oracle
+                .tobin_tax(denom.as_str())
+                .and_then(|rate| to_binary(&TobinTaxResponse { rate }).map_err(|e| e.to_string())),
+            (TerraRoute::Oracle, TerraQuery::TobinTaxes {}) => to_binary(&TobinTaxesResponse {
+                tobin_taxes: self
+                    .oracle
+                    .vote_targets()
+                    .into_iter()
+                    .map(|denom| TobinTaxItem {
+                        tobin_tax: self.oracle.tobin_taxes[&denom],
+                        denom,
+                    })
+                    .collect(),
+            })
+            .map_err(|e| e.to_string()),
+            (TerraRoute::Oracle, TerraQuery::OracleParameters {}) => self
+                .oracle
+                .parameters
+                .as_ref()
+                .ok_or_else(|| "oracle parameters not set".to_string())
+                .and_then(|params| to_binary(params).map_err(|e| e.to_string())),
+            (TerraRoute::Oracle, TerraQuery::MissCounter { validator }) => {
+                to_binary(&MissCounterResponse {
+                    miss_counter: self
+                        .oracle
+                        .miss_counters
+                        .get(validator)
+                        .copied()
+                        .unwrap_or_default(),
+                })
+                .map_err(|e| e.to_string())
+            }
+            // validators feed prices themselves unless they delegated it
+            (TerraRoute::Oracle, TerraQuery::FeederDelegation { validator }) => {
+                to_binary(&FeederDelegationResponse {
+                    feeder: self
+                        .oracle
+                        .feeder_delegations
+                        .get(validator)
+                        .unwrap_or(validator)
+                        .to_string(),
+                })
+                .map_err(|e| e.to_string())
+            }
+            (TerraRoute::Wasm, TerraQuery::ContractInfo { contract_address }) => self
+                .wasm
+                .contract_infos
+                .get(contract_address)
+                .ok_or_else(|| format!("contract {}: not found", contract_address))
+                .and_then(|info| to_binary(info).map_err(|e| e.to_string())),
+            (TerraRoute::Wasm, TerraQuery::CodeInfo { code_id }) => self
+                .wasm
+                .code_infos
+                .get(code_id)
+                .ok_or_else(|| format!("code id {}: not found", code_id))
+                .and_then(|info| to_binary(info).map_err(|e| e.to_string())),
+            (
+                TerraRoute::Wasm,
+                TerraQuery::RawStore {
+                    contract_address,
+                    key,
+                },
+            ) => to_binary(&RawStoreResponse {
+                data: self
+                    .wasm
+                    .raw_stores
+                    .get(contract_address)
+                    .and_then(|store| store.get(key.as_slice()))
+                    .cloned()
+                    .unwrap_or_default()
+                    .into(),
+            })
+            .map_err(|e| e.to_string()),
+            (TerraRoute::Wasm, TerraQuery::WasmParameters {}) => self
+                .wasm
+                .parameters
+                .as_ref()
+                .ok_or_else(|| "wasm parameters not set".to_string())
+                .and_then(|params| to_binary(params).map_err(|e| e.to_string())),
+            _ => {
+                return SystemResult::Err(SystemError::UnsupportedRequest {
+                    kind: format!("{:?} query on {:?} route", query_data, route),
+                })
+            }
+        };
+
+        SystemResult::Ok(ContractResult::from(res))
+    }
+
+    // With market parameters set, swaps are simulated like the chain's market module does,
+    // otherwise the offer is converted at the oracle exchange rate without any spread.
+    fn swap(&self, offer_coin: &Coin, ask_denom: &str) -> Result<SwapResponse, String> {
+        match &self.market.parameters {
+            Some(market_parameters) => {
+                let market = MarketSnapshot {
+                    luna_exchange_rates: self
+                        .oracle
+                        .actives()
+                        .into_iter()
+                        .map(|denom| ExchangeRateItem {
+                            exchange_rate: self.oracle.exchange_rates["uluna"][&denom],
+                            quote_denom: denom,
+                        })
+                        .collect(),
+                    tobin_taxes: self
+                        .oracle
+                        .vote_targets()
+                        .into_iter()
+                        .map(|denom| TobinTaxItem {
+                            tobin_tax: self.oracle.tobin_taxes[&denom],
+                            denom,
+                        })
+                        .collect(),
+                    market_parameters: market_parameters.clone(),
+                    terra_pool_delta: self.market.terra_pool_delta,
+                };
+
+                simulate_swap(&market, offer_coin, ask_denom)
+                    .map(|simulation| simulation.swap)
+                    .map_err(|e| e.to_string())
+            }
+            None => self
+                .oracle
+                .exchange_rate(&offer_coin.denom, ask_denom)
+                .and_then(|rate| {
+                    let amount = Uint256::from(offer_coin.amount) * Decimal256::from(rate);
+                    Ok(SwapResponse {
+                        receive: Coin {
+                            denom: ask_denom.to_string(),
+                            amount: Uint128::try_from(amount).map_err(|e| e.to_string())?,
+                        },
+                    })
+                }),
+        }
+    }
+
+    // set a new terra pool delta and return the old one
+    pub fn update_terra_pool_delta(
+        &mut self,
+        terra_pool_delta: SignedDecimal256,
+    ) -> SignedDecimal256 {
+        std::mem::replace(&mut self.market.terra_pool_delta, terra_pool_delta)
+    }
+
+    // set new market parameters and return the old ones
+    pub fn update_market_parameters(
+        &mut self,
+        parameters: MarketParametersResponse,
+    ) -> Option<MarketParametersResponse> {
+        self.market.parameters.replace(parameters)
+    }
+
+    // set exchange rates of the given quote denoms against base_denom
+    pub fn update_exchange_rates(&mut self, base_denom: &str, rates: &[(&str, Decimal)]) {
+        let entry = self
+            .oracle
+            .exchange_rates
+            .entry(base_denom.to_string())
+            .or_default();
+        for (quote_denom, rate) in rates {
+            entry.insert(quote_denom.to_string(), *rate);
+        }
+    }
+
+    // set tobin taxes per denom, these denoms become the oracle vote targets
+    pub fn update_tobin_taxes(&mut self, tobin_taxes: &[(&str, Decimal)]) {
+        for (denom, tobin_tax) in tobin_taxes {
+            self.oracle
+                .tobin_taxes
+                .insert(denom.to_string(), *tobin_tax);
+        }
+    }
+
+    // set new oracle parameters and return the old ones
+    pub fn update_oracle_parameters(
+        &mut self,
+        parameters: OracleParametersResponse,
+    ) -> Option<OracleParametersResponse> {
+        self.oracle.parameters.replace(parameters)
+    }
+
+    // set a new miss counter for the given validator and return the old one
+    pub fn update_miss_counter(&mut self, validator: &str, miss_counter: u64) -> Option<u64> {
+        self.oracle
+            .miss_counters
+            .insert(validator.to_string(), miss_counter)
+    }
+
+    // delegate the price feeding of the given validator and return the old feeder
+    pub fn update_feeder_delegation(&mut self, validator: &str, feeder: &str) -> Option<String> {
+        self.oracle
+            .feeder_delegations
+            .insert(validator.to_string(), feeder.to_string())
+    }
+
+    // set a new tax rate and return the old one
+    pub fn update_tax_rate(&mut self, rate: Decimal) -> Decimal {
+        std::mem::replace(&mut self.treasury.tax_rate, rate)
+    }
+
+    // set tax caps per denom, denoms without a cap are answered with the cap of the
+    // tax policy of the treasury parameters, like the chain does
+    pub fn update_tax_caps(&mut self, caps: &[(&str, Uint128)]) {
+        for (denom, cap) in caps {
+            self.treasury.tax_caps.insert(denom.to_string(), *cap);
+        }
+    }
+
+    // set a new reward weight and return the old one
+    pub fn update_reward_weight(&mut self, reward_weight: Decimal) -> Decimal {
+        std::mem::replace(&mut self.treasury.reward_weight, reward_weight)
+    }
+
+    // set new seigniorage proceeds and return the old ones
+    pub fn update_seigniorage_proceeds(&mut self, seigniorage_proceeds: Uint128) -> Uint128 {
+        std::mem::replace(
+            &mut self.treasury.seigniorage_proceeds,
+            seigniorage_proceeds,
+        )
+    }
+
+    // set new tax proceeds and return the old ones
+    pub fn update_tax_proceeds(&mut self, tax_proceeds: Vec<Coin>) -> Vec<Coin> {
+        std::mem::replace(&mut self.treasury.tax_proceeds, tax_proceeds)
+    }
+
+    // set new treasury parameters and return the old ones
+    pub fn update_treasury_parameters(
+        &mut self,
+        parameters: TreasuryParametersResponse,
+    ) -> Option<TreasuryParametersResponse> {
+        self.treasury.parameters.replace(parameters)
+    }
+
+    // set new treasury indicators and return the old ones
+    pub fn update_indicators(
+        &mut self,
+        indicators: IndicatorsResponse,
+    ) -> Option<IndicatorsResponse> {
+        self.treasury.indicators.replace(indicators)
+    }
+
+    // set a new contract info for the given address and return the old one
+    pub fn update_contract_info(
+        &mut self,
+        contract_info: ContractInfoResponse,
+    ) -> Option<ContractInfoResponse> {
+        self.wasm
+            .contract_infos
+            .insert(contract_info.address.clone(), contract_info)
+    }
+
+    // set a new code info for its code id and return the old one
+    pub fn update_code_info(&mut self, code_info: CodeInfoResponse) -> Option<CodeInfoResponse> {
+        self.wasm.code_infos.insert(code_info.code_id, code_info)
+    }
+
+    // set a raw value in the store of the given contract and return the old value
+    pub fn update_raw_store(
+        &mut self,
+        contract_address: &str,
+        key: &[u8],
+        value: &[u8],
+    ) -> Option<Vec<u8>> {
+        self.wasm
+            .raw_stores
+            .entry(contract_address.to_string())
+            .or_default()
+            .insert(key.to_vec(), value.to_vec())
+    }
+
+    // set new wasm parameters and return the old ones
+    pub fn update_wasm_parameters(
+        &mut self,
+        parameters: WasmParametersResponse,
+    ) -> Option<WasmParametersResponse> {
+        self.wasm.parameters.replace(parameters)
+    }
+
+    // set a new balance for the given address and return the old balance
+    pub fn update_balance(
+        &mut self,
+        addr: impl Into<String>,
+        balance: Vec<Coin>,
+    ) -> Option<Vec<Coin>> {
+        self.base.update_balance(addr, balance)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::TerraError;
+    use crate::querier::TerraQuerier;
+    use crate::query::PolicyConstraints;
+    use cosmwasm_std::{coin, Binary, QuerierWrapper, StdError};
+
+    #[test]
+    fn terra_mock_api() {
+        let deps = mock_dependencies_with_terra_api(&[]);
+        let creator = mock_terra_address("creator");
+        assert!(creator.starts_with("terra1"));
+        assert_ne!(creator, mock_terra_address("owner"));
+
+        let canonical = deps.api.addr_canonicalize(&creator).unwrap();
+        assert_eq!(canonical.len(), 20);
+        assert_eq!(deps.api.addr_humanize(&canonical).unwrap(), creator);
+        assert_eq!(deps.api.addr_validate(&creator).unwrap(), creator);
+        assert!(deps.api.addr_validate("creator").is_err());
+    }
+
+    #[test]
+    fn query_exchange_rates_and_swap() {
+        let mut deps = mock_dependencies_with_terra(&[]);
+        deps.querier.update_exchange_rates(
+            "uluna",
+            &[
+                ("uusd", Decimal::percent(5000)),
+                ("ukrw", Decimal::percent(6_000_000)),
+            ],
+        );
+
+        let querier = QuerierWrapper::new(&deps.querier);
+        let terra_querier = TerraQuerier::new(&querier);
+
+        let res = terra_querier
+            .query_exchange_rates("uluna", vec!["uusd", "ukrw"])
+            .unwrap();
+        assert_eq!(
+            res,
+            ExchangeRatesResponse {
+                base_denom: "uluna".to_string(),
+                exchange_rates: vec![
+                    ExchangeRateItem {
+                        quote_denom: "uusd".to_string(),
+                        exchange_rate: Decimal::percent(5000),
+                    },
+                    ExchangeRateItem {
+                        quote_denom: "ukrw".to_string(),
+                        exchange_rate: Decimal::percent(6_000_000),
+                    },
+                ],
+            }
+        );
+
+        let res = terra_querier
+            .query_swap(coin(1_000_000, "uluna"), "uusd")
+            .unwrap();
+        assert_eq!(res.receive, coin(50_000_000, "uusd"));
+
+        // a return beyond Uint128::MAX is an error, not a panic
+        match terra_querier.query_swap(coin(u128::MAX, "uluna"), "ukrw") {
+            Err(TerraError::Std(StdError::GenericErr { msg, .. })) => {
+                assert!(msg.contains("Uint128"), "{}", msg)
+            }
+            res => panic!("Unexpected result: {:?}", res),
+        }
+
+        match terra_querier.query_exchange_rates("uluna", vec!["umnt"]) {
+            Err(TerraError::UnknownDenom { msg }) => assert!(msg.contains("unknown denom")),
+            res => panic!("Unexpected result: {:?}", res),
+        }
+    }
+
+    #[test]
+    fn query_market() {
+        let mut deps = mock_dependencies_with_terra(&[]);
+        let params = MarketParametersResponse {
+            base_pool: Decimal::from_ratio(250_000_000_000u128, 1u128),
+            pool_recovery_period: 36,
+            min_stability_spread: Decimal::permille(20),
+        };
+        deps.querier.update_market_parameters(params.clone());
+        deps.querier
+            .update_terra_pool_delta(SignedDecimal256::new(Decimal256::percent(150), true));
+
+        let querier = QuerierWrapper::new(&deps.querier);
+        let terra_querier = TerraQuerier::new(&querier);
+
+        assert_eq!(terra_querier.query_market_parameters().unwrap(), params);
+        assert_eq!(
+            terra_querier
+                .query_terra_pool_delta()
+                .unwrap()
+                .terra_pool_delta
+                .to_string(),
+            "-1.5"
+        );
+    }
+
+    #[test]
+    fn query_swap_simulated_with_market_parameters() {
+        let mut deps = mock_dependencies_with_terra(&[]);
+        deps.querier.update_exchange_rates(
+            "uluna",
+            &[
+                ("usdr", Decimal::percent(4050)),
+                ("uusd", Decimal::percent(5840)),
+            ],
+        );
+        deps.querier.update_tobin_taxes(&[
+            ("usdr", Decimal::permille(3)),
+            ("uusd", Decimal::permille(3)),
+        ]);
+        deps.querier
+            .update_market_parameters(MarketParametersResponse {
+                base_pool: Decimal::from_ratio(50_000_000_000_000u128, 1u128),
+                pool_recovery_period: 36,
+                min_stability_spread: Decimal::permille(20),
+            });
+
+        let querier = QuerierWrapper::new(&deps.querier);
+        let terra_querier = TerraQuerier::new(&querier);
+
+        // min stability spread of 2% applies
+        assert_eq!(
+            terra_querier
+                .query_swap(coin(1_000_000, "uluna"), "uusd")
+                .unwrap()
+                .receive,
+            coin(57_232_000, "uusd")
+        );
+        // tobin tax of 0.3% applies
+        assert_eq!(
+            terra_querier
+                .query_swap(coin(58_400_000, "uusd"), "usdr")
+                .unwrap()
+                .receive,
+            coin(40_378_500, "usdr")
+        );
+    }
+
+    #[test]
+    fn query_oracle() {
+        let mut deps = mock_dependencies_with_terra(&[]);
+        deps.querier.update_exchange_rates(
+            "uluna",
+            &[
+                ("uusd", Decimal::percent(5000)),
+                ("ukrw", Decimal::percent(6_000_000)),
+            ],
+        );
+        deps.querier.update_tobin_taxes(&[
+            ("uusd", Decimal::permille(3)),
+            ("umnt", Decimal::permille(20)),
+        ]);
+        deps.querier.update_miss_counter("validator0000", 12);
+        deps.querier
+            .update_feeder_delegation("validator0000", "feeder0000");
+
+        let querier = QuerierWrapper::new(&deps.querier);
+        let terra_querier = TerraQuerier::new(&querier);
+
+        assert_eq!(
+            terra_querier
+                .query_exchange_rate("uluna", "ukrw")
+                .unwrap()
+                .exchange_rate,
+            Decimal::percent(6_000_000)
+        );
+        assert_eq!(
+            terra_querier.query_actives().unwrap().actives,
+            vec!["ukrw".to_string(), "uusd".to_string()]
+        );
+        assert_eq!(
+            terra_querier.query_vote_targets().unwrap().vote_targets,
+            vec!["umnt".to_string(), "uusd".to_string()]
+        );
+        assert_eq!(
+            terra_querier.query_tobin_tax("umnt").unwrap().rate,
+            Decimal::permille(20)
+        );
+        assert!(terra_querier.query_tobin_tax("ukrw").is_err());
+        assert_eq!(
+            terra_querier.query_tobin_taxes().unwrap().tobin_taxes,
+            vec![
+                TobinTaxItem {
+                    denom: "umnt".to_string(),
+                    tobin_tax: Decimal::permille(20),
+                },
+                TobinTaxItem {
+                    denom: "uusd".to_string(),
+                    tobin_tax: Decimal::permille(3),
+                },
+            ]
+        );
+        assert!(terra_querier.query_oracle_parameters().is_err());
+        assert_eq!(
+            terra_querier
+                .query_miss_counter("validator0000")
+                .unwrap()
+                .miss_counter,
+            12
+        );
+        assert_eq!(
+            terra_querier
+                .query_feeder_delegation("validator0000")
+                .unwrap()
+                .feeder,
+            "feeder0000"
+        );
+        assert_eq!(
+            terra_querier
+                .query_feeder_delegation("validator0001")
+                .unwrap()
+                .feeder,
+            "validator0001"
+        );
+    }
+
+    #[test]
+    fn query_tax() {
+        let mut deps = mock_dependencies_with_terra(&[]);
+        deps.querier.update_tax_rate(Decimal::permille(5));
+        deps.querier
+            .update_tax_caps(&[("uusd", Uint128::from(1_000_000u128))]);
+
+        let querier = QuerierWrapper::new(&deps.querier);
+        let terra_querier = TerraQuerier::new(&querier);
+
+        assert_eq!(
+            terra_querier.query_tax_rate().unwrap().rate,
+            Decimal::permille(5)
+        );
+        assert_eq!(
+            terra_querier.query_tax_cap("uusd").unwrap().cap,
+            Uint128::from(1_000_000u128)
+        );
+        match terra_querier.query_tax_cap("ukrw").unwrap_err() {
+            TerraError::Std(StdError::GenericErr { msg, .. }) => assert_eq!(
+                msg,
+                "Querier contract error: ukrw: no tax cap set and treasury parameters not set"
+            ),
+            e => panic!("Unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn tax_cap_defaults_to_the_tax_policy() {
+        let mut deps = mock_dependencies_with_terra(&[]);
+        deps.querier
+            .update_tax_caps(&[("uusd", Uint128::from(1_000_000u128))]);
+        let policy = PolicyConstraints {
+            rate_min: Decimal::zero(),
+            rate_max: Decimal::percent(1),
+            cap: coin(1_000_000_000, "usdr"),
+            change_rate_max: Decimal::permille(1),
+        };
+        deps.querier
+            .update_treasury_parameters(TreasuryParametersResponse {
+                tax_policy: policy.clone(),
+                reward_policy: policy,
+                seigniorage_burden_target: Decimal::percent(67),
+                mining_increment: Decimal::permille(1007),
+                window_short: 4,
+                window_long: 52,
+                window_probation: 12,
+            });
+
+        let querier = QuerierWrapper::new(&deps.querier);
+        let terra_querier = TerraQuerier::new(&querier);
+
+        assert_eq!(
+            terra_querier.query_tax_cap("uusd").unwrap().cap,
+            Uint128::from(1_000_000u128)
+        );
+        assert_eq!(
+            terra_querier.query_tax_cap("ukrw").unwrap().cap,
+            Uint128::from(1_000_000_000u128)
+        );
+    }
+
+    #[test]
+    fn query_treasury() {
+        let mut deps = mock_dependencies_with_terra(&[]);
+        let policy = PolicyConstraints {
+            rate_min: Decimal::permille(1),
+            rate_max: Decimal::percent(1),
+            cap: coin(1_000_000, "usdr"),
+            change_rate_max: Decimal::permille(1),
+        };
+        let params = TreasuryParametersResponse {
+            tax_policy: policy.clone(),
+            reward_policy: policy,
+            seigniorage_burden_target: Decimal::percent(67),
+            mining_increment: Decimal::permille(1007),
+            window_short: 4,
+            window_long: 52,
+            window_probation: 12,
+        };
+        deps.querier.update_reward_weight(Decimal::percent(5));
+        deps.querier
+            .update_seigniorage_proceeds(Uint128::from(1_234u128));
+        deps.querier
+            .update_tax_proceeds(vec![coin(100, "ukrw"), coin(200, "uusd")]);
+        deps.querier.update_treasury_parameters(params.clone());
+
+        let querier = QuerierWrapper::new(&deps.querier);
+        let terra_querier = TerraQuerier::new(&querier);
+
+        assert_eq!(
+            terra_querier.query_reward_weight().unwrap().reward_weight,
+            Decimal::percent(5)
+        );
+        assert_eq!(
+            terra_querier
+                .query_seigniorage_proceeds()
+                .unwrap()
+                .seigniorage_proceeds,
+            Uint128::from(1_234u128)
+        );
+        assert_eq!(
+            terra_querier.query_tax_proceeds().unwrap().tax_proceeds,
+            vec![coin(100, "ukrw"), coin(200, "uusd")]
+        );
+        assert_eq!(terra_querier.query_treasury_parameters().unwrap(), params);
+        assert!(terra_querier.query_indicators().is_err());
+    }
+
+    #[test]
+    fn query_contract_info() {
+        let mut deps = mock_dependencies_with_terra(&[]);
+        let info = ContractInfoResponse {
+            address: "contract0000".to_string(),
+            creator: "creator0000".to_string(),
+            code_id: 3,
+            admin: None,
+        };
+        deps.querier.update_contract_info(info.clone());
+
+        let querier = QuerierWrapper::new(&deps.querier);
+        let terra_querier = TerraQuerier::new(&querier);
+
+        assert_eq!(
+            terra_querier.query_contract_info("contract0000").unwrap(),
+            info
+        );
+        assert!(terra_querier.query_contract_info("contract0001").is_err());
+    }
+
+    #[test]
+    fn query_wasm() {
+        let mut deps = mock_dependencies_with_terra(&[]);
+        let info = CodeInfoResponse {
+            code_id: 7,
+            creator: "creator0000".to_string(),
+            code_hash: Binary::from(b"hash".to_vec()),
+        };
+        let params = WasmParametersResponse {
+            max_contract_size: 600 * 1024,
+            max_contract_gas: 20_000_000,
+            max_contract_msg_size: 4096,
+        };
+        deps.querier.update_code_info(info.clone());
+        deps.querier
+            .update_raw_store("contract0000", b"config", b"{}");
+        deps.querier.update_wasm_parameters(params.clone());
+
+        let querier = QuerierWrapper::new(&deps.querier);
+        let terra_querier = TerraQuerier::new(&querier);
+
+        assert_eq!(terra_querier.query_code_info(7).unwrap(), info);
+        assert!(terra_querier.query_code_info(8).is_err());
+        assert_eq!(
+            terra_querier
+                .query_raw_store("contract0000", Binary::from(b"config".to_vec()))
+                .unwrap()
+                .data,
+            Binary::from(b"{}".to_vec())
+        );
+        assert!(terra_querier
+            .query_raw_store("contract0000", Binary::from(b"state".to_vec()))
+            .unwrap()
+            .data
+            .is_empty());
+        assert_eq!(terra_querier.query_wasm_parameters().unwrap(), params);
+    }
+
+    #[test]
+    fn forwards_bank_queries() {
+        let deps = mock_dependencies_with_terra(&[coin(123, "uusd")]);
+
+        let querier = QuerierWrapper::new(&deps.querier);
+        assert_eq!(
+            querier.query_balance(MOCK_CONTRACT_ADDR, "uusd").unwrap(),
+            coin(123, "uusd")
+        );
+    }
+}